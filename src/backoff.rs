@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(doc)]
+use crate::{Job, Queue};
+
+/// Strategy to calculate the time to wait before a retry attempt with.
+///
+/// The delays are derived from the retry delay (see [`Job::retry_delay`] and [`Queue::retry_delay`]),
+/// which is referred to as _base_ below, and the number of the retry attempt.
+/// If the strategy is not specified for a job, the one of its queue is used, and if it is not specified
+/// for the queue either, [`Backoff::Exponential`] is used for jobs with `retry_backoff` enabled,
+/// and [`Backoff::Fixed`] otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Backoff {
+    /// Wait for _base_ before every retry attempt.
+    #[default]
+    Fixed,
+
+    /// Wait for _base_ multiplied by the number of the retry attempt,
+    /// i.e. _base_, 2 × _base_, 3 × _base_, and so on.
+    Linear,
+
+    /// Double the delay with every retry attempt, adding a random jitter of up to 100%,
+    /// i.e. _base_ to 2 × _base_, 2 × _base_ to 4 × _base_, and so on.
+    ///
+    /// This is what PgBoss uses when `retry_backoff` is enabled.
+    Exponential,
+
+    /// Just like [`Backoff::Exponential`], but never wait longer than `max_delay`.
+    CappedExponential {
+        /// Upper bound for the delay.
+        #[serde(with = "secs")]
+        max_delay: Duration,
+    },
+
+    /// Wait for a random time between _base_ and three times the previous delay,
+    /// but never longer than `max_delay`.
+    ///
+    /// See [Exponential Backoff And Jitter](https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/).
+    DecorrelatedJitter {
+        /// Upper bound for the delay.
+        #[serde(with = "secs")]
+        max_delay: Duration,
+    },
+}
+
+/// Stores durations as whole seconds, just like the rest of the retry settings.
+mod secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub(super) fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(value.as_secs())
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
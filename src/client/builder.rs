@@ -7,12 +7,14 @@ use crate::{utils, Error};
 #[derive(Debug, Clone)]
 pub struct ClientBuilder {
     schema: String,
+    notify: bool,
 }
 
 impl Default for ClientBuilder {
     fn default() -> Self {
         ClientBuilder {
             schema: "pgboss".to_string(),
+            notify: false,
         }
     }
 }
@@ -27,6 +29,18 @@ impl ClientBuilder {
         self
     }
 
+    /// Whether to notify consumers when sending a job.
+    ///
+    /// If set to `true`, sending a job that is visible to consumers right away
+    /// will wake up those waiting on a [`Listener`](crate::Listener) for this queue.
+    /// Likewise, completing, cancelling, or failing a job will wake up those waiting
+    /// for it with [`Client::wait_for_job`](crate::Client::wait_for_job).
+    /// Defaults to `false`.
+    pub fn notify(mut self, value: bool) -> Self {
+        self.notify = value;
+        self
+    }
+
     /// Connect to the PostgreSQL server.
     pub async fn connect(self) -> Result<Client, Error> {
         let pool = utils::create_pool(None).await?;
@@ -49,6 +63,7 @@ impl ClientBuilder {
     pub async fn with_pool(self, pool: PgPool) -> Result<Client, Error> {
         let opts = opts::ClientOptions {
             schema: self.schema,
+            notify: self.notify,
         };
         Ok(Client::new(pool, opts).await?)
     }
@@ -1,5 +1,8 @@
+use crate::listener::DoneListener;
 use crate::{sql, App};
 use sqlx::postgres::PgPool;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 
 mod builder;
 mod opts;
@@ -11,33 +14,99 @@ pub use builder::ClientBuilder;
 struct Statements {
     fetch_jobs: String,
     get_job_info: String,
+    get_jobs_info: String,
+    query_jobs: String,
+    get_child_jobs: String,
     delete_jobs: String,
     fail_jobs: String,
+    fail_jobs_permanently: String,
+    expire_jobs: String,
+    archive_jobs: String,
+    delete_archived_jobs: String,
+    try_lock_maintenance: String,
+    check_if_maintenance_due: String,
+    set_maintained_on: String,
+    get_schedules: String,
+    upsert_schedule: String,
+    delete_schedule: String,
+    try_lock_cron: String,
+    get_cron_on: String,
+    set_cron_on: String,
+    subscribe: String,
+    unsubscribe: String,
+    get_subscribed_queues: String,
+    publish: String,
     cancel_jobs: String,
     complete_jobs: String,
     resume_jobs: String,
+    retry_jobs: String,
+    release_jobs: String,
+    touch_jobs: String,
+    report_progress: String,
     create_job: String,
+    create_jobs: String,
     create_queue: String,
     get_queue: String,
     get_queues: String,
+    lock_queue: String,
+    update_queue: String,
+    set_queue_paused: String,
+    get_queue_size: String,
+    get_queue_stats: String,
     delete_queue: String,
+    purge_queue: String,
+    get_notify_channels: String,
+    get_done_channel: String,
 }
 
 impl Statements {
-    fn for_schema(name: &str) -> Statements {
+    fn for_schema(name: &str, notify: bool) -> Statements {
         Statements {
             fetch_jobs: sql::dml::fetch_jobs(name),
             get_job_info: sql::dml::get_job_info(name),
+            get_jobs_info: sql::dml::get_jobs_info(name),
+            query_jobs: sql::dml::query_jobs(name),
+            get_child_jobs: sql::dml::get_child_jobs(name),
             delete_jobs: sql::dml::delete_jobs(name),
             create_job: sql::proc::create_job(name),
-            fail_jobs: sql::dml::fail_jobs(name),
-            cancel_jobs: sql::dml::cancel_jobs(name),
+            create_jobs: sql::proc::create_jobs(name),
+            fail_jobs: sql::dml::fail_jobs(name, notify),
+            fail_jobs_permanently: sql::dml::fail_jobs_permanently(name, notify),
+            expire_jobs: sql::dml::expire_jobs(name, notify),
+            archive_jobs: sql::dml::archive_jobs(name),
+            delete_archived_jobs: sql::dml::delete_archived_jobs(name),
+            try_lock_maintenance: sql::dml::try_lock_maintenance(name),
+            check_if_maintenance_due: sql::dml::check_if_maintenance_due(name),
+            set_maintained_on: sql::dml::set_maintained_on(name),
+            get_schedules: sql::dml::get_schedules(name),
+            upsert_schedule: sql::dml::upsert_schedule(name),
+            delete_schedule: sql::dml::delete_schedule(name),
+            try_lock_cron: sql::dml::try_lock_cron(name),
+            get_cron_on: sql::dml::get_cron_on(name),
+            set_cron_on: sql::dml::set_cron_on(name),
+            subscribe: sql::dml::subscribe(name),
+            unsubscribe: sql::dml::unsubscribe(name),
+            get_subscribed_queues: sql::dml::get_subscribed_queues(name),
+            publish: sql::dml::publish(name),
+            cancel_jobs: sql::dml::cancel_jobs(name, notify),
             resume_jobs: sql::dml::resume_jobs(name),
-            complete_jobs: sql::dml::complete_jobs(name),
+            retry_jobs: sql::dml::retry_jobs(name),
+            release_jobs: sql::dml::release_jobs(name),
+            touch_jobs: sql::dml::touch_jobs(name),
+            report_progress: sql::dml::report_progress(name),
+            complete_jobs: sql::dml::complete_jobs(name, notify),
             create_queue: sql::proc::create_queue(name),
             get_queue: sql::dml::get_queue(name),
             get_queues: sql::dml::get_queues(name),
+            lock_queue: sql::dml::lock_queue(name),
+            update_queue: sql::dml::update_queue(name),
+            set_queue_paused: sql::dml::set_queue_paused(name),
+            get_queue_size: sql::dml::get_queue_size(name),
+            get_queue_stats: sql::dml::get_queue_stats(name),
             delete_queue: sql::proc::delete_queue(name),
+            purge_queue: sql::dml::purge_queue(name),
+            get_notify_channels: sql::dml::get_notify_channels(name),
+            get_done_channel: sql::dml::get_done_channel(name),
         }
     }
 }
@@ -48,12 +117,19 @@ pub struct Client {
     pool: PgPool,
     opts: opts::ClientOptions,
     stmt: Statements,
+    /// Started on first use, see [`Client::wait_for_job`].
+    done_listener: Arc<OnceCell<DoneListener>>,
 }
 
 impl Client {
     async fn new(pool: PgPool, opts: opts::ClientOptions) -> Result<Self, sqlx::Error> {
-        let stmt = Statements::for_schema(&opts.schema);
-        let mut c = Client { pool, opts, stmt };
+        let stmt = Statements::for_schema(&opts.schema, opts.notify);
+        let mut c = Client {
+            pool,
+            opts,
+            stmt,
+            done_listener: Arc::default(),
+        };
         c.init().await?;
         Ok(c)
     }
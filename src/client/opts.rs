@@ -1,12 +1,14 @@
 #[derive(Debug, Clone)]
 pub(crate) struct ClientOptions {
     pub(crate) schema: String,
+    pub(crate) notify: bool,
 }
 
 impl Default for ClientOptions {
     fn default() -> Self {
         ClientOptions {
             schema: "pgboss".to_string(),
+            notify: false,
         }
     }
 }
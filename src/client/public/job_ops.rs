@@ -1,10 +1,15 @@
 use super::Client;
-use crate::job::{Job, JobDetails};
+use crate::job::{Job, JobDetails, RetryOptions};
 use crate::Error;
+use crate::Heartbeat;
 use crate::JobOptions;
+use crate::JobQuery;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 use sqlx::types::Json;
+use sqlx::{Acquire, Postgres};
 use std::borrow::Borrow;
+use std::time::Duration;
 use uuid::Uuid;
 
 impl Client {
@@ -21,76 +26,248 @@ impl Client {
     where
         J: Borrow<Job<'a>>,
     {
+        self.send_job_in(&self.pool, job).await
+    }
+
+    /// Enqueue a job using this connection.
+    ///
+    /// Works just like [`Client::send_job`], but lets you pass a connection, a pool,
+    /// or a transaction. In the latter case, the job will only become visible to consumers
+    /// once the transaction has been committed, and will be discarded on rollback, e.g.:
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # use pgboss::{Client, Job};
+    /// # let c = Client::connect().await.unwrap();
+    /// # let pool = sqlx::PgPool::connect("postgres://localhost:5432").await.unwrap();
+    /// let mut tx = pool.begin().await.unwrap();
+    /// sqlx::query("INSERT INTO orders (id) VALUES (1)")
+    ///     .execute(&mut *tx)
+    ///     .await
+    ///     .unwrap();
+    /// let job = Job::builder().queue_name("order_placed").build();
+    /// c.send_job_in(&mut tx, &job).await.unwrap();
+    /// tx.commit().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn send_job_in<'a, 'c, A, J>(&self, conn: A, job: J) -> Result<Uuid, Error>
+    where
+        A: Acquire<'c, Database = Postgres>,
+        J: Borrow<Job<'a>>,
+    {
+        let mut conn = conn.acquire().await?;
         let job = job.borrow();
+        let mut opts = job.opts();
+        opts.notify = self.opts.notify.then_some(true);
         let id: Option<Uuid> = sqlx::query_scalar(&self.stmt.create_job)
             .bind(job.id)
             .bind(job.queue_name)
             .bind(Json(&job.data))
-            .bind(Json(&job.opts()))
-            .fetch_one(&self.pool)
+            .bind(Json(&opts))
+            .fetch_one(&mut *conn)
             .await
-            .map_err(|e| {
-                if let Some(db_error) = e.as_database_error() {
-                    if let Some(constraint) = db_error.constraint() {
-                        if constraint.starts_with('j') {
-                            if constraint.ends_with("_pkey") {
-                                return Error::Conflict {
-                                    msg: "job with this id already exists",
-                                };
-                            }
-                            if constraint.ends_with("_i1") {
-                                return Error::Throttled {
-                                    msg: "policy 'short' is applied to jobs with state 'created'",
-                                };
-                            }
-                            if constraint.ends_with("_i2") {
-                                return Error::Throttled {
-                                    msg: "policy 'singleton' is applied to jobs with state 'active'",
-                                };
-                            }
-                            if constraint.ends_with("_i3") {
-                                return Error::Throttled {
-                                    msg: "policy 'stately' is applied to jobs with state 'created', 'retry' or 'active'",
-                                };
-                            }
-                            if constraint.ends_with("_i4") {
-                                return Error::Throttled {
-                                    msg: "singleton policy applied to jobs with 'singleton_on' property and state not 'cancelled'",
-                                };
-                            }
-                        }
-                        if constraint == "dlq_fkey" {
-                            return Error::DoesNotExist {
-                                msg: "dead letter queue does not exist",
-                            };
-                        }
-                    }
-                }
-                Error::Sqlx(e)
-            })?;
+            .map_err(create_job_error)?;
         id.ok_or(Error::DoesNotExist {
             msg: "queue does not exist",
         })
     }
 
+    /// Enqueue numerous jobs in one go.
+    ///
+    /// All the jobs are created by a single statement, with the queue defaults applied
+    /// just like with [`Client::send_job`], which makes this way more efficient than sending
+    /// the jobs one by one. The jobs may be sent to different queues.
+    ///
+    /// Either all the jobs are created, or none of them is, in which case the error is
+    /// returned just like with [`Client::send_job`], e.g. [`Error::DoesNotExist`] if any of
+    /// the queues does not exist, or [`Error::Throttled`] if any of the jobs has been throttled.
+    ///
+    /// Returns the jobs' IDs in the same order the jobs have been provided.
+    pub async fn send_jobs<'a, J>(&self, jobs: &[J]) -> Result<Vec<Uuid>, Error>
+    where
+        J: Borrow<Job<'a>>,
+    {
+        self.send_jobs_in(&self.pool, jobs).await
+    }
+
+    /// Enqueue numerous jobs in one go using this connection.
+    ///
+    /// Works just like [`Client::send_jobs`], but lets you pass a connection, a pool,
+    /// or a transaction (see [`Client::send_job_in`]).
+    pub async fn send_jobs_in<'a, 'c, A, J>(&self, conn: A, jobs: &[J]) -> Result<Vec<Uuid>, Error>
+    where
+        A: Acquire<'c, Database = Postgres>,
+        J: Borrow<Job<'a>>,
+    {
+        let mut ids = Vec::with_capacity(jobs.len());
+        let mut queue_names = Vec::with_capacity(jobs.len());
+        let mut data = Vec::with_capacity(jobs.len());
+        let mut opts = Vec::with_capacity(jobs.len());
+        for job in jobs {
+            let job = job.borrow();
+            let mut job_opts = job.opts();
+            job_opts.notify = self.opts.notify.then_some(true);
+            ids.push(job.id);
+            queue_names.push(job.queue_name);
+            data.push(Json(&job.data));
+            opts.push(Json(job_opts));
+        }
+        // a savepoint, if we have been given a transaction
+        let mut tx = conn.begin().await?;
+        let created: Vec<Option<Uuid>> = sqlx::query_scalar(&self.stmt.create_jobs)
+            .bind(ids)
+            .bind(queue_names)
+            .bind(data)
+            .bind(opts)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(create_job_error)?;
+        let created =
+            created
+                .into_iter()
+                .collect::<Option<Vec<Uuid>>>()
+                .ok_or(Error::DoesNotExist {
+                    msg: "queue does not exist",
+                })?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
     /// Create and enqueue a job.
+    ///
+    /// The `data` can be any serializable value (including [`serde_json::Value`])
+    /// and will be stored as the job's payload.
     pub async fn send_data<Q, D>(&self, queue_name: Q, data: D) -> Result<Uuid, Error>
     where
         Q: AsRef<str>,
-        D: Borrow<serde_json::Value>,
+        D: Serialize,
     {
+        self.send_data_in(&self.pool, queue_name, data).await
+    }
+
+    /// Create and enqueue a job using this connection.
+    ///
+    /// Works just like [`Client::send_data`], but lets you pass a connection, a pool,
+    /// or a transaction (see [`Client::send_job_in`]).
+    pub async fn send_data_in<'c, A, Q, D>(
+        &self,
+        conn: A,
+        queue_name: Q,
+        data: D,
+    ) -> Result<Uuid, Error>
+    where
+        A: Acquire<'c, Database = Postgres>,
+        Q: AsRef<str>,
+        D: Serialize,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut opts = JobOptions::default();
+        opts.notify = self.opts.notify.then_some(true);
         let id: Option<Uuid> = sqlx::query_scalar(&self.stmt.create_job)
             .bind(Option::<Uuid>::None)
             .bind(queue_name.as_ref())
-            .bind(Json(data.borrow()))
-            .bind(Json(JobOptions::default()))
-            .fetch_one(&self.pool)
+            .bind(Json(data))
+            .bind(Json(opts))
+            .fetch_one(&mut *conn)
             .await?;
         id.ok_or(Error::DoesNotExist {
             msg: "queue does not exist",
         })
     }
 
+    /// Create and enqueue a job, unless a job has already been sent within this period.
+    ///
+    /// Time is divided into slots of `period` (rounded down to whole seconds, but at least
+    /// a second long, so shorter periods are treated as one second) and only one job is
+    /// allowed per slot (and per `key`, if provided). Will return [`Error::Throttled`]
+    /// if the current slot has already been taken. Use [`Client::send_debounced`] to defer
+    /// the job to the next slot instead.
+    pub async fn send_throttled<Q, D>(
+        &self,
+        queue_name: Q,
+        data: D,
+        period: Duration,
+        key: Option<&str>,
+    ) -> Result<Uuid, Error>
+    where
+        Q: AsRef<str>,
+        D: Serialize,
+    {
+        self.send_singleton(queue_name.as_ref(), data, period, key, false)
+            .await
+    }
+
+    /// Create and enqueue a job, deferring it to the next slot if a job has already been
+    /// sent within this period.
+    ///
+    /// Works just like [`Client::send_throttled`], but if the current slot has already been
+    /// taken, the job will be put into the next slot and only become visible to consumers
+    /// once that slot begins. Will return [`Error::Throttled`] if the next slot has already
+    /// been taken too. Periods shorter than a second are treated as one second.
+    pub async fn send_debounced<Q, D>(
+        &self,
+        queue_name: Q,
+        data: D,
+        period: Duration,
+        key: Option<&str>,
+    ) -> Result<Uuid, Error>
+    where
+        Q: AsRef<str>,
+        D: Serialize,
+    {
+        self.send_singleton(queue_name.as_ref(), data, period, key, true)
+            .await
+    }
+
+    async fn send_singleton<D>(
+        &self,
+        queue_name: &str,
+        data: D,
+        period: Duration,
+        key: Option<&str>,
+        next_slot: bool,
+    ) -> Result<Uuid, Error>
+    where
+        D: Serialize,
+    {
+        // the period is stored in whole seconds and must not round down to zero
+        let period = period.max(Duration::from_secs(1));
+        let mut opts = JobOptions::default();
+        opts.notify = self.opts.notify.then_some(true);
+        opts.singleton_for = Some(period);
+        opts.singleton_key = key;
+        let id = match self.create_job_with_opts(queue_name, &data, &opts).await {
+            Err(Error::Throttled { .. }) if next_slot => {
+                opts.singleton_offset = Some(period);
+                self.create_job_with_opts(queue_name, &data, &opts).await?
+            }
+            other => other?,
+        };
+        id.ok_or(Error::DoesNotExist {
+            msg: "queue does not exist",
+        })
+    }
+
+    async fn create_job_with_opts<D>(
+        &self,
+        queue_name: &str,
+        data: D,
+        opts: &JobOptions<'_>,
+    ) -> Result<Option<Uuid>, Error>
+    where
+        D: Serialize,
+    {
+        let id: Option<Uuid> = sqlx::query_scalar(&self.stmt.create_job)
+            .bind(Option::<Uuid>::None)
+            .bind(queue_name)
+            .bind(Json(data))
+            .bind(Json(opts))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(create_job_error)?;
+        Ok(id)
+    }
+
     /// Fetch a job from a queue.
     pub async fn fetch_job<Q>(&self, queue_name: Q) -> Result<Option<JobDetails>, Error>
     where
@@ -104,6 +281,22 @@ impl Client {
         Ok(maybe_job)
     }
 
+    /// Fetch a job from a queue deserializing its payload into `T`.
+    ///
+    /// If the payload cannot be deserialized, [`Error::Payload`] is returned,
+    /// while the job - just like with [`Client::fetch_job`] - has been consumed
+    /// and is now `active`, so you will most likely want to fail it.
+    pub async fn fetch_typed_job<T, Q>(&self, queue_name: Q) -> Result<Option<JobDetails<T>>, Error>
+    where
+        T: DeserializeOwned,
+        Q: AsRef<str>,
+    {
+        match self.fetch_job(queue_name).await? {
+            None => Ok(None),
+            Some(job) => job.try_into_typed().map(Some),
+        }
+    }
+
     /// Get this job's details including metadata.
     ///
     /// Unlike [`Client::fetch_job`] _will not consume_ a job from the queue,
@@ -122,6 +315,100 @@ impl Client {
         Ok(maybe_job)
     }
 
+    /// Get details of numerous jobs in this queue.
+    ///
+    /// Works just like [`Client::get_job`], but for a batch of IDs. Only the jobs
+    /// which have been found are returned, in the order their IDs have been provided.
+    ///
+    /// Jobs which have already been moved to the archive are not returned,
+    /// use [`Client::get_jobs_with_archive`] to also look them up there.
+    pub async fn get_jobs<Q, I>(&self, queue_name: Q, job_ids: I) -> Result<Vec<JobDetails>, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.get_jobs_info(Some(queue_name.as_ref()), job_ids, false)
+            .await
+    }
+
+    /// Get details of numerous jobs in this queue, including archived ones.
+    ///
+    /// Works just like [`Client::get_jobs`], but falls back to the archive
+    /// for jobs no longer present in the queue.
+    pub async fn get_jobs_with_archive<Q, I>(
+        &self,
+        queue_name: Q,
+        job_ids: I,
+    ) -> Result<Vec<JobDetails>, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.get_jobs_info(Some(queue_name.as_ref()), job_ids, true)
+            .await
+    }
+
+    /// Get details of numerous jobs regardless of the queue they belong to.
+    ///
+    /// Use [`JobDetails::queue_name`] to tell which queue a job has been found in.
+    pub async fn get_jobs_by_ids<I>(&self, job_ids: I) -> Result<Vec<JobDetails>, Error>
+    where
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.get_jobs_info(None, job_ids, false).await
+    }
+
+    /// Get details of numerous jobs regardless of the queue they belong to, including archived ones.
+    pub async fn get_jobs_by_ids_with_archive<I>(
+        &self,
+        job_ids: I,
+    ) -> Result<Vec<JobDetails>, Error>
+    where
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.get_jobs_info(None, job_ids, true).await
+    }
+
+    /// Get details of the jobs depending on this job.
+    ///
+    /// See [`Job::parents`]. Note that jobs which have already been archived are not listed.
+    pub async fn get_child_jobs(&self, job_id: Uuid) -> Result<Vec<JobDetails>, Error> {
+        let jobs: Vec<JobDetails> = sqlx::query_as(&self.stmt.get_child_jobs)
+            .bind(job_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(jobs)
+    }
+
+    /// Create a [`JobQuery`] to list jobs matching some criteria.
+    pub fn query_jobs(&self) -> JobQuery {
+        JobQuery::new(self.clone())
+    }
+
+    pub(crate) async fn fetch_jobs_page(&self, query: &JobQuery) -> Result<Vec<JobDetails>, Error> {
+        let states = (!query.states.is_empty()).then(|| {
+            query
+                .states
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+        });
+        let jobs: Vec<JobDetails> = sqlx::query_as(&self.stmt.query_jobs)
+            .bind(query.queue_name.as_deref())
+            .bind(states)
+            .bind(query.created_after)
+            .bind(query.created_before)
+            .bind(query.singleton_key.as_deref())
+            .bind(query.dead_letter.as_deref())
+            .bind(query.data_contains.as_ref().map(Json))
+            .bind(query.after.map(|c| c.created_at))
+            .bind(query.after.map(|c| c.id))
+            .bind(query.limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(jobs)
+    }
+
     /// Fetch a batch of jobs.
     pub async fn fetch_jobs<Q>(
         &self,
@@ -178,6 +465,7 @@ impl Client {
     {
         let count = self
             .update_jobs_returning_affected_count(
+                &self.pool,
                 queue_name,
                 [job_id],
                 Some(json!({})),
@@ -221,6 +509,7 @@ impl Client {
         O: Into<serde_json::Value>,
     {
         self.update_jobs_returning_affected_count(
+            &self.pool,
             queue_name,
             job_ids,
             Some(details.into()),
@@ -229,6 +518,54 @@ impl Client {
         .await
     }
 
+    /// Mark a job as `failed` skipping any remaining retry attempts.
+    ///
+    /// Will call [`Client::fail_jobs_permanently`] internally.
+    pub async fn fail_job_permanently<Q, O>(
+        &self,
+        queue_name: Q,
+        job_id: Uuid,
+        details: O,
+    ) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+        O: Into<serde_json::Value>,
+    {
+        let count = self
+            .fail_jobs_permanently(queue_name, [job_id], details)
+            .await?;
+        Ok(count == 1)
+    }
+
+    /// Mark numerous jobs as `failed` skipping any remaining retry attempts.
+    ///
+    /// Useful when retrying is known to be pointless, e.g. because the job's payload
+    /// is invalid. Unlike [`Client::fail_jobs_with_details`], the jobs are not retried
+    /// regardless of their retry limit, and so are sent to their dead letter queues (if any)
+    /// right away, with `details` (e.g. the reason) stored as their `output`.
+    ///
+    /// Returns the number of jobs marked as `failed`.
+    pub async fn fail_jobs_permanently<Q, I, O>(
+        &self,
+        queue_name: Q,
+        job_ids: I,
+        details: O,
+    ) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+        O: Into<serde_json::Value>,
+    {
+        self.update_jobs_returning_affected_count(
+            &self.pool,
+            queue_name,
+            job_ids,
+            Some(details.into()),
+            &self.stmt.fail_jobs_permanently,
+        )
+        .await
+    }
+
     /// Mark numerous jobs as `failed`.
     pub async fn fail_jobs<Q, I>(&self, queue_name: Q, job_ids: I) -> Result<usize, Error>
     where
@@ -236,6 +573,7 @@ impl Client {
         I: IntoIterator<Item = Uuid>,
     {
         self.update_jobs_returning_affected_count(
+            &self.pool,
             queue_name,
             job_ids,
             Some(json!({})),
@@ -276,8 +614,30 @@ impl Client {
         Q: AsRef<str>,
         I: IntoIterator<Item = Uuid>,
         O: Into<serde_json::Value>,
+    {
+        self.complete_jobs_in(&self.pool, queue_name, job_ids, details)
+            .await
+    }
+
+    /// Mark numerous jobs as `completed` using this connection.
+    ///
+    /// Works just like [`Client::complete_jobs`], but lets you pass a connection, a pool,
+    /// or a transaction (see [`Client::send_job_in`]).
+    pub async fn complete_jobs_in<'c, A, Q, I, O>(
+        &self,
+        conn: A,
+        queue_name: Q,
+        job_ids: I,
+        details: O,
+    ) -> Result<usize, Error>
+    where
+        A: Acquire<'c, Database = Postgres>,
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+        O: Into<serde_json::Value>,
     {
         self.update_jobs_returning_affected_count(
+            conn,
             queue_name,
             job_ids,
             Some(details.into()),
@@ -307,8 +667,32 @@ impl Client {
         Q: AsRef<str>,
         I: IntoIterator<Item = Uuid>,
     {
-        self.update_jobs_returning_affected_count(queue_name, job_ids, None, &self.stmt.cancel_jobs)
-            .await
+        self.cancel_jobs_in(&self.pool, queue_name, job_ids).await
+    }
+
+    /// Mark numerous jobs as `cancelled` using this connection.
+    ///
+    /// Works just like [`Client::cancel_jobs`], but lets you pass a connection, a pool,
+    /// or a transaction (see [`Client::send_job_in`]).
+    pub async fn cancel_jobs_in<'c, A, Q, I>(
+        &self,
+        conn: A,
+        queue_name: Q,
+        job_ids: I,
+    ) -> Result<usize, Error>
+    where
+        A: Acquire<'c, Database = Postgres>,
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.update_jobs_returning_affected_count(
+            conn,
+            queue_name,
+            job_ids,
+            None,
+            &self.stmt.cancel_jobs,
+        )
+        .await
     }
 
     /// Mark a cancelled job (See [`Client::cancel_job`]) as `created` again.
@@ -332,21 +716,231 @@ impl Client {
         Q: AsRef<str>,
         I: IntoIterator<Item = Uuid>,
     {
-        self.update_jobs_returning_affected_count(queue_name, job_ids, None, &self.stmt.resume_jobs)
+        self.update_jobs_returning_affected_count(
+            &self.pool,
+            queue_name,
+            job_ids,
+            None,
+            &self.stmt.resume_jobs,
+        )
+        .await
+    }
+
+    /// Move a `failed` job back to the queue.
+    ///
+    /// Will call [`Client::retry_jobs`] internally.
+    pub async fn retry_job<Q>(&self, queue_name: Q, job_id: Uuid) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let count = self.retry_jobs(queue_name, [job_id]).await?;
+        Ok(count == 1)
+    }
+
+    /// Move numerous `failed` jobs back to the queue.
+    ///
+    /// The jobs are marked as `created` with their retry count reset, so that
+    /// they get all the attempts as per their retry limit. Use [`Client::retry_jobs_with`]
+    /// to also re-run `completed` jobs or to preserve the retry count.
+    ///
+    /// Returns the number of jobs moved back to the queue, where `0` means
+    /// there are no failed jobs with these ids in the queue or no such queue.
+    pub async fn retry_jobs<Q, I>(&self, queue_name: Q, job_ids: I) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.retry_jobs_with(queue_name, job_ids, RetryOptions::default())
+            .await
+    }
+
+    /// Move numerous `failed` or `completed` jobs back to the queue.
+    ///
+    /// If the retry count is preserved, jobs which have already been retried
+    /// are marked as `retry` rather than `created`. Will return [`Error::Throttled`]
+    /// if the queue's policy does not allow for one of these jobs to be queued again.
+    pub async fn retry_jobs_with<Q, I>(
+        &self,
+        queue_name: Q,
+        job_ids: I,
+        opts: RetryOptions,
+    ) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        let count: (i64,) = sqlx::query_as(&self.stmt.retry_jobs)
+            .bind(queue_name.as_ref())
+            .bind(job_ids.into_iter().collect::<Vec<Uuid>>())
+            .bind(opts.include_completed)
+            .bind(opts.reset_retry_count)
+            .fetch_one(&self.pool)
             .await
+            .map_err(create_job_error)?;
+        Ok(count.0 as usize)
+    }
+
+    /// Release an `active` job back to the queue.
+    ///
+    /// Will call [`Client::release_jobs`] internally.
+    pub async fn release_job<Q>(&self, queue_name: Q, job_id: Uuid) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let released = self.release_jobs(queue_name, [job_id]).await?;
+        Ok(released.len() == 1)
+    }
+
+    /// Release numerous `active` jobs back to the queue.
+    ///
+    /// Useful when a consumer is shutting down and cannot finish the jobs it has fetched:
+    /// the jobs are transitioned to `created` (or `retry`, if they have already been retried)
+    /// and become visible to consumers immediately, rather than staying `active` until they expire.
+    /// Releasing a job does not count as a retry attempt.
+    ///
+    /// Returns IDs of the jobs that have actually been released, i.e. jobs that
+    /// are not `active` or do not exist are skipped.
+    pub async fn release_jobs<Q, I>(&self, queue_name: Q, job_ids: I) -> Result<Vec<Uuid>, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        let ids: Vec<Uuid> = sqlx::query_scalar(&self.stmt.release_jobs)
+            .bind(queue_name.as_ref())
+            .bind(job_ids.into_iter().collect::<Vec<Uuid>>())
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(ids)
+    }
+
+    /// Extend the lease of an `active` job.
+    ///
+    /// Will call [`Client::touch_jobs`] internally.
+    pub async fn touch_job<Q>(&self, queue_name: Q, job_id: Uuid) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let count = self.touch_jobs(queue_name, [job_id]).await?;
+        Ok(count == 1)
     }
 
-    async fn update_jobs_returning_affected_count<Q, I>(
+    /// Extend the lease of numerous `active` jobs.
+    ///
+    /// A job may only stay `active` for [`JobDetails::expire_in`] since it has been
+    /// started. Touching a job resets its `started_at` to the current time, so that the
+    /// job will only expire if it is not completed (or touched again) within `expire_in`.
+    /// Note that the time the job was actually fetched at is lost with the first touch.
+    /// This allows using short expiration periods for long-running jobs, as long as the
+    /// worker keeps touching them. See also [`Client::heartbeat`].
+    ///
+    /// In a happy path, returns the number of jobs touched, where `0` means there are no
+    /// `active` jobs with these ids in the queue or no such queue.
+    pub async fn touch_jobs<Q, I>(&self, queue_name: Q, job_ids: I) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+        I: IntoIterator<Item = Uuid>,
+    {
+        self.update_jobs_returning_affected_count(
+            &self.pool,
+            queue_name,
+            job_ids,
+            None,
+            &self.stmt.touch_jobs,
+        )
+        .await
+    }
+
+    /// Report progress of an `active` job.
+    ///
+    /// The value is stored as the job's `output` and so is available via [`Client::get_job`]
+    /// while the job is being processed, e.g. `json!({"rows_imported": 42, "rows_total": 100})`.
+    /// Note that the progress is overwritten by the output of [`Client::complete_job`]
+    /// or [`Client::fail_job`].
+    ///
+    /// Returns `false` if there is no such `active` job in the queue, in which case
+    /// the job's `output` is left intact.
+    pub async fn report_progress<Q, O>(
         &self,
         queue_name: Q,
+        job_id: Uuid,
+        value: O,
+    ) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+        O: Into<serde_json::Value>,
+    {
+        let count = self
+            .update_jobs_returning_affected_count(
+                &self.pool,
+                queue_name,
+                [job_id],
+                Some(value.into()),
+                &self.stmt.report_progress,
+            )
+            .await?;
+        Ok(count == 1)
+    }
+
+    /// Keep touching an `active` job in the background.
+    ///
+    /// Will call [`Client::touch_job`] every `interval` until the returned [`Heartbeat`]
+    /// is dropped or the job is no longer `active`. The `interval` should be well
+    /// below the job's [`JobDetails::expire_in`], e.g. half of it.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # use pgboss::Client;
+    /// # let c = Client::connect().await.unwrap();
+    /// let job = c.fetch_job("qname").await.unwrap().unwrap();
+    /// let heartbeat = c.heartbeat("qname", job.id, job.expire_in / 2);
+    /// // ... long-running processing ...
+    /// drop(heartbeat);
+    /// c.complete_job("qname", job.id, serde_json::json!({})).await.unwrap();
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Will panic if called outside of the context of a Tokio runtime.
+    pub fn heartbeat<Q>(&self, queue_name: Q, job_id: Uuid, interval: Duration) -> Heartbeat
+    where
+        Q: Into<String>,
+    {
+        Heartbeat::start(self.clone(), queue_name.into(), job_id, interval)
+    }
+
+    async fn get_jobs_info<I>(
+        &self,
+        queue_name: Option<&str>,
+        job_ids: I,
+        with_archive: bool,
+    ) -> Result<Vec<JobDetails>, Error>
+    where
+        I: IntoIterator<Item = Uuid>,
+    {
+        let jobs: Vec<JobDetails> = sqlx::query_as(&self.stmt.get_jobs_info)
+            .bind(queue_name)
+            .bind(job_ids.into_iter().collect::<Vec<Uuid>>())
+            .bind(with_archive)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(jobs)
+    }
+
+    async fn update_jobs_returning_affected_count<'c, A, Q, I>(
+        &self,
+        conn: A,
+        queue_name: Q,
         job_ids: I,
         details: Option<serde_json::Value>,
         q: &str,
     ) -> Result<usize, Error>
     where
+        A: Acquire<'c, Database = Postgres>,
         Q: AsRef<str>,
         I: IntoIterator<Item = Uuid>,
     {
+        let mut conn = conn.acquire().await?;
         let q = sqlx::query_as(q)
             .bind(queue_name.as_ref())
             .bind(job_ids.into_iter().collect::<Vec<Uuid>>());
@@ -354,7 +948,48 @@ impl Client {
             Some(d) => q.bind(d),
             None => q,
         };
-        let count: (i64,) = q.fetch_one(&self.pool).await?;
+        let count: (i64,) = q.fetch_one(&mut *conn).await?;
         Ok(count.0 as usize)
     }
 }
+
+/// Maps errors raised by the `create_job` function to more specific ones.
+pub(super) fn create_job_error(e: sqlx::Error) -> Error {
+    if let Some(db_error) = e.as_database_error() {
+        if let Some(constraint) = db_error.constraint() {
+            if constraint.starts_with('j') {
+                if constraint.ends_with("_pkey") {
+                    return Error::Conflict {
+                        msg: "job with this id already exists",
+                    };
+                }
+                if constraint.ends_with("_i1") {
+                    return Error::Throttled {
+                        msg: "policy 'short' is applied to jobs with state 'created'",
+                    };
+                }
+                if constraint.ends_with("_i2") {
+                    return Error::Throttled {
+                        msg: "policy 'singleton' is applied to jobs with state 'active'",
+                    };
+                }
+                if constraint.ends_with("_i3") {
+                    return Error::Throttled {
+                            msg: "policy 'stately' is applied to jobs with state 'created', 'retry' or 'active'",
+                        };
+                }
+                if constraint.ends_with("_i4") {
+                    return Error::Throttled {
+                            msg: "singleton policy applied to jobs with 'singleton_on' property and state not 'cancelled'",
+                        };
+                }
+            }
+            if constraint == "dlq_fkey" {
+                return Error::DoesNotExist {
+                    msg: "dead letter queue does not exist",
+                };
+            }
+        }
+    }
+    Error::Sqlx(e)
+}
@@ -0,0 +1,130 @@
+use super::Client;
+use crate::listener::{DoneListener, DoneWaiter};
+use crate::{Error, JobDetails, JobState, Listener};
+use std::time::Duration;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+#[cfg(doc)]
+use crate::ClientBuilder;
+
+/// How often to check on a job being waited for.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How often to check on a job being waited for when notifications are enabled.
+///
+/// Still polling, since the job may be done by a client not sending notifications.
+const LISTEN_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+impl Client {
+    /// Start listening for jobs sent to these queues.
+    ///
+    /// This will open a dedicated connection to the PostgreSQL server using
+    /// the client's connect options, which is not taken from (and does not count
+    /// towards the size of) the client's pool. Only jobs sent by clients with
+    /// [`ClientBuilder::notify`] enabled will wake up the [`Listener`].
+    pub async fn listen<I, Q>(&self, queue_names: I) -> Result<Listener, Error>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: AsRef<str>,
+    {
+        let queue_names: Vec<String> = queue_names
+            .into_iter()
+            .map(|q| q.as_ref().to_string())
+            .collect();
+        let channels: Vec<String> = sqlx::query_scalar(&self.stmt.get_notify_channels)
+            .bind(queue_names)
+            .fetch_all(&self.pool)
+            .await?;
+        Listener::connect(&self.pool, channels).await
+    }
+
+    /// Wait for a job to be done, i.e. `completed`, `cancelled`, or `failed`.
+    ///
+    /// Resolves with the job's final details (including its `output`) as soon as the job
+    /// has reached one of these states, or with `None` once the `timeout` has elapsed.
+    /// Jobs which have been archived in the meantime are also looked up in the archive.
+    /// Will return [`Error::DoesNotExist`] if there is no such job in the queue.
+    ///
+    /// If this client has been built with [`ClientBuilder::notify`] enabled, it gets notified
+    /// of jobs being done by clients with notifications enabled. For that, a dedicated
+    /// connection is opened on first use and shared by all the waits of this client (and
+    /// its clones). In any case, the job is also polled for, e.g. to learn about jobs done
+    /// by a client without notifications.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use pgboss::{Client, JobState};
+    ///
+    /// let c = Client::builder().notify(true).connect().await.unwrap();
+    /// let id = c.send_data("qname", serde_json::json!({})).await.unwrap();
+    /// match c.wait_for_job("qname", id, Duration::from_secs(30)).await.unwrap() {
+    ///     Some(job) if job.state == JobState::Completed => { /* use job.output */ }
+    ///     Some(_) => { /* cancelled or failed */ }
+    ///     None => { /* still in progress */ }
+    /// }
+    /// # });
+    /// ```
+    pub async fn wait_for_job<Q>(
+        &self,
+        queue_name: Q,
+        job_id: Uuid,
+        timeout: Duration,
+    ) -> Result<Option<JobDetails>, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let queue_name = queue_name.as_ref();
+        let deadline = Instant::now() + timeout;
+        // listening before checking on the job, so that no notification is missed
+        let waiter = match self.opts.notify {
+            false => None,
+            true => match self.done_waiter(job_id).await {
+                Ok(waiter) => Some(waiter),
+                Err(e) => {
+                    log::warn!("Failed to listen, falling back to polling: {:?}", e);
+                    None
+                }
+            },
+        };
+        loop {
+            let job = self
+                .get_jobs_with_archive(queue_name, [job_id])
+                .await?
+                .pop()
+                .ok_or(Error::DoesNotExist {
+                    msg: "job does not exist",
+                })?;
+            if matches!(
+                job.state,
+                JobState::Completed | JobState::Cancelled | JobState::Failed
+            ) {
+                return Ok(Some(job));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            match &waiter {
+                Some(waiter) => {
+                    waiter.wait(LISTEN_POLL_INTERVAL.min(deadline - now)).await;
+                }
+                None => tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await,
+            }
+        }
+    }
+
+    async fn done_waiter(&self, job_id: Uuid) -> Result<DoneWaiter, Error> {
+        let listener = self
+            .done_listener
+            .get_or_try_init(|| async {
+                let channel: String = sqlx::query_scalar(&self.stmt.get_done_channel)
+                    .fetch_one(&self.pool)
+                    .await?;
+                DoneListener::connect(&self.pool, &channel).await
+            })
+            .await?;
+        Ok(listener.waiter(job_id))
+    }
+}
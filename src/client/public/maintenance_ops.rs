@@ -0,0 +1,95 @@
+use super::Client;
+use crate::{Error, MaintenanceDetails};
+use std::time::Duration;
+
+impl Client {
+    /// Fail `active` jobs that have not been completed within their `expire_in`.
+    ///
+    /// Expired jobs go through the same path as [`Client::fail_jobs`] do: they
+    /// are retried if their retry limit allows, otherwise marked as `failed`
+    /// and copied to the dead letter queue (if any). The reason is recorded in
+    /// the job's `output` as `{"message": "job failed by timeout in active state"}`.
+    ///
+    /// This operation covers all the queues and returns the number of expired jobs.
+    pub async fn expire_jobs(&self) -> Result<usize, Error> {
+        let count: (i64,) = sqlx::query_as(&self.stmt.expire_jobs)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0 as usize)
+    }
+
+    /// Move `completed`, `cancelled` and `failed` jobs past their `keep_until` to the archive.
+    ///
+    /// Unless specified for the job explicitly (see [`JobBuilder::retain_for`](crate::JobBuilder::retain_for)),
+    /// `keep_until` is derived from the queue's retention policy. Archived jobs are no longer
+    /// accessible via [`Client::get_job`].
+    ///
+    /// This operation covers all the queues and returns the number of archived jobs.
+    pub async fn archive_jobs(&self) -> Result<usize, Error> {
+        let count: (i64,) = sqlx::query_as(&self.stmt.archive_jobs)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0 as usize)
+    }
+
+    /// Permanently delete jobs that have been archived more than `older_than` ago.
+    ///
+    /// Returns the number of deleted jobs.
+    pub async fn delete_archived_jobs(&self, older_than: Duration) -> Result<usize, Error> {
+        let count: (i64,) = sqlx::query_as(&self.stmt.delete_archived_jobs)
+            .bind(older_than.as_secs_f64())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0 as usize)
+    }
+
+    /// Run all the maintenance routines one after another.
+    ///
+    /// That is: expire overdue jobs (see [`Client::expire_jobs`]), archive jobs
+    /// that are past their retention (see [`Client::archive_jobs`]) and delete
+    /// jobs that have been archived more than `delete_after` ago
+    /// (see [`Client::delete_archived_jobs`]).
+    pub async fn maintain(&self, delete_after: Duration) -> Result<MaintenanceDetails, Error> {
+        let expired_jobs = self.expire_jobs().await?;
+        let archived_jobs = self.archive_jobs().await?;
+        let deleted_jobs = self.delete_archived_jobs(delete_after).await?;
+        Ok(MaintenanceDetails {
+            expired_jobs,
+            archived_jobs,
+            deleted_jobs,
+        })
+    }
+
+    /// Run [`Client::maintain`] unless another process is doing so or has done so recently.
+    ///
+    /// Maintenance is considered due if it has not been performed within `interval`.
+    /// Returns `None` if maintenance has been skipped.
+    pub(crate) async fn maintain_if_due(
+        &self,
+        interval: Duration,
+        delete_after: Duration,
+    ) -> Result<Option<MaintenanceDetails>, Error> {
+        let mut tx = self.pool.begin().await?;
+        let locked: bool = sqlx::query_scalar(&self.stmt.try_lock_maintenance)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !locked {
+            return Ok(None);
+        }
+        let due: bool = sqlx::query_scalar(&self.stmt.check_if_maintenance_due)
+            .bind(interval.as_secs_f64())
+            .fetch_one(&mut *tx)
+            .await?;
+        if !due {
+            return Ok(None);
+        }
+        // the lock is held (and others skip maintenance) until the transaction ends,
+        // and if the maintenance fails, `maintained_on` is left intact
+        let details = self.maintain(delete_after).await?;
+        sqlx::query(&self.stmt.set_maintained_on)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some(details))
+    }
+}
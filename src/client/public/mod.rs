@@ -1,11 +1,26 @@
 mod connect_ops;
 mod job_ops;
+mod listen_ops;
+mod maintenance_ops;
+mod pubsub_ops;
 mod queue_ops;
+mod schedule_ops;
 use super::{builder::ClientBuilder, opts, Client};
+use crate::{SupervisorBuilder, WorkerBuilder};
 
 impl Client {
     /// Create an instance of [`ClientBuilder`]
     pub fn builder() -> ClientBuilder {
         ClientBuilder::default()
     }
+
+    /// Create an instance of [`WorkerBuilder`] for this client.
+    pub fn worker(&self) -> WorkerBuilder {
+        WorkerBuilder::new(self.clone())
+    }
+
+    /// Create an instance of [`SupervisorBuilder`] for this client.
+    pub fn supervisor(&self) -> SupervisorBuilder {
+        SupervisorBuilder::new(self.clone())
+    }
 }
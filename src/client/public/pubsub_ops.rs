@@ -0,0 +1,122 @@
+use super::job_ops::create_job_error;
+use super::Client;
+use crate::job::Job;
+use crate::Error;
+use crate::JobOptions;
+use serde::Serialize;
+use sqlx::types::Json;
+use std::borrow::Borrow;
+use uuid::Uuid;
+
+impl Client {
+    /// Subscribe the queue with this name to an event.
+    ///
+    /// Every job published for this `event` (see [`Client::publish`]) will be
+    /// sent to this queue. Subscribing twice is not an error.
+    ///
+    /// If the queue does not exist, [`Error::DoesNotExist`] will be returned.
+    pub async fn subscribe<E, Q>(&self, event: E, queue_name: Q) -> Result<(), Error>
+    where
+        E: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        sqlx::query(&self.stmt.subscribe)
+            .bind(event.as_ref())
+            .bind(queue_name.as_ref())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                if let Some(db_error) = e.as_database_error() {
+                    if db_error.constraint() == Some("subscription_name_fkey") {
+                        return Error::DoesNotExist {
+                            msg: "queue does not exist",
+                        };
+                    }
+                }
+                Error::Sqlx(e)
+            })?;
+        Ok(())
+    }
+
+    /// Unsubscribe the queue with this name from an event.
+    ///
+    /// Returns `false` if the queue was not subscribed to this event.
+    pub async fn unsubscribe<E, Q>(&self, event: E, queue_name: Q) -> Result<bool, Error>
+    where
+        E: AsRef<str>,
+        Q: AsRef<str>,
+    {
+        let result = sqlx::query(&self.stmt.unsubscribe)
+            .bind(event.as_ref())
+            .bind(queue_name.as_ref())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Names of the queues subscribed to this event.
+    pub async fn get_subscribed_queues<E>(&self, event: E) -> Result<Vec<String>, Error>
+    where
+        E: AsRef<str>,
+    {
+        let names: Vec<String> = sqlx::query_scalar(&self.stmt.get_subscribed_queues)
+            .bind(event.as_ref())
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(names)
+    }
+
+    /// Send a copy of the job to every queue subscribed to this event.
+    ///
+    /// The job's [`Job::queue_name`] and [`Job::id`] are not used, while all the other
+    /// options are applied to each copy. The jobs are created in a single statement,
+    /// so either all the subscribed queues get the job, or none of them does.
+    ///
+    /// Returns IDs of the created jobs ordered by queue name, which will be empty
+    /// if no queue is subscribed to this event.
+    pub async fn publish<'a, E, J>(&self, event: E, job: J) -> Result<Vec<Uuid>, Error>
+    where
+        E: AsRef<str>,
+        J: Borrow<Job<'a>>,
+    {
+        let job = job.borrow();
+        let mut opts = job.opts();
+        opts.notify = self.opts.notify.then_some(true);
+        self.publish_with_opts(event.as_ref(), Json(&job.data), opts)
+            .await
+    }
+
+    /// Create and send a job to every queue subscribed to this event.
+    ///
+    /// Works just like [`Client::publish`], but only takes the job's payload,
+    /// which can be any serializable value (including [`serde_json::Value`]).
+    pub async fn publish_data<E, D>(&self, event: E, data: D) -> Result<Vec<Uuid>, Error>
+    where
+        E: AsRef<str>,
+        D: Serialize,
+    {
+        let mut opts = JobOptions::default();
+        opts.notify = self.opts.notify.then_some(true);
+        self.publish_with_opts(event.as_ref(), Json(data), opts)
+            .await
+    }
+
+    async fn publish_with_opts<D>(
+        &self,
+        event: &str,
+        data: Json<D>,
+        opts: JobOptions<'_>,
+    ) -> Result<Vec<Uuid>, Error>
+    where
+        D: Serialize,
+    {
+        let ids: Vec<Option<Uuid>> = sqlx::query_scalar(&self.stmt.publish)
+            .bind(event)
+            .bind(data)
+            .bind(Json(opts))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(create_job_error)?;
+        Ok(ids.into_iter().flatten().collect())
+    }
+}
@@ -1,7 +1,8 @@
 use super::Client;
 use crate::queue::Queue;
 use crate::Error;
-use crate::QueueDetails;
+use crate::JobState;
+use crate::{QueueDetails, QueueStats, QueueUpdate};
 use sqlx::types::Json;
 use std::borrow::Borrow;
 
@@ -51,6 +52,90 @@ impl Client {
         Ok(queues)
     }
 
+    /// Updates configuration of an existing queue.
+    ///
+    /// The queue's settings are replaced with the ones from `opts` (matched by [`Queue::name`]),
+    /// where unset options are reset to defaults, just like if the queue was created with
+    /// these options in the first place. Note that the settings are applied to jobs sent
+    /// after the update, while jobs already in the queue keep their retry, expiration,
+    /// retention, and dead letter settings.
+    ///
+    /// Returns [`Error::DoesNotExist`] if either the queue or its dead letter queue does not exist.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # use pgboss::{Client, Queue};
+    /// # let c = Client::connect().await.unwrap();
+    /// let queue = Queue::builder().name("qname").retry_limit(5).build();
+    /// let update = c.update_queue(&queue).await.unwrap();
+    /// println!("changed: {:?}", update.changes());
+    /// # });
+    /// ```
+    pub async fn update_queue<'a, Q>(&self, opts: Q) -> Result<QueueUpdate, Error>
+    where
+        Q: Borrow<Queue<'a>>,
+    {
+        let q_opts = opts.borrow();
+        let mut tx = self.pool.begin().await?;
+        let before: Option<QueueDetails> = sqlx::query_as(&self.stmt.lock_queue)
+            .bind(q_opts.name)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let before = before.ok_or(Error::DoesNotExist {
+            msg: "queue does not exist",
+        })?;
+        let after: QueueDetails = sqlx::query_as(&self.stmt.update_queue)
+            .bind(q_opts.name)
+            .bind(Json(q_opts))
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                if let Some(db_error) = e.as_database_error() {
+                    if db_error.constraint() == Some("queue_dead_letter_fkey") {
+                        return Error::DoesNotExist {
+                            msg: "dead letter queue does not exist",
+                        };
+                    }
+                }
+                Error::Sqlx(e)
+            })?;
+        tx.commit().await?;
+        Ok(QueueUpdate { before, after })
+    }
+
+    /// Stops consumers from fetching jobs from the queue.
+    ///
+    /// Jobs can still be sent to a paused queue, while jobs which are already `active`
+    /// can be completed or failed as usual. Use [`Client::resume_queue`] to let consumers
+    /// fetch jobs again.
+    ///
+    /// Returns `false` if the queue does not exist.
+    pub async fn pause_queue<Q>(&self, queue_name: Q) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        self.set_queue_paused(queue_name.as_ref(), true).await
+    }
+
+    /// Lets consumers fetch jobs from a queue paused with [`Client::pause_queue`].
+    ///
+    /// Returns `false` if the queue does not exist.
+    pub async fn resume_queue<Q>(&self, queue_name: Q) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        self.set_queue_paused(queue_name.as_ref(), false).await
+    }
+
+    async fn set_queue_paused(&self, queue_name: &str, paused: bool) -> Result<bool, Error> {
+        let result = sqlx::query(&self.stmt.set_queue_paused)
+            .bind(queue_name)
+            .bind(paused)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
     /// Deletes a named queue.
     ///
     /// Deletes a queue and all jobs from the active job table.
@@ -65,4 +150,87 @@ impl Client {
             .await
             .map(|_| ())?)
     }
+
+    /// Deletes all the jobs waiting in the queue, leaving the queue itself intact.
+    ///
+    /// Only jobs in `created` or `retry` state are deleted, while `active` jobs
+    /// and jobs in terminal states stay. Use [`Client::purge_queue_by_states`] to
+    /// specify the states yourself.
+    ///
+    /// Returns the number of deleted jobs, where `0` means either there are no jobs
+    /// waiting in the queue or the queue does not exist.
+    pub async fn purge_queue<Q>(&self, queue_name: Q) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+    {
+        self.purge_queue_by_states(queue_name, [JobState::Created, JobState::Retry])
+            .await
+    }
+
+    /// Deletes jobs in any of these states from the queue, leaving the queue itself intact.
+    ///
+    /// Note that nothing stops you from purging `active` jobs this way, but then
+    /// consumers processing these jobs will not be able to complete or fail them.
+    ///
+    /// Returns the number of deleted jobs.
+    pub async fn purge_queue_by_states<Q, S>(
+        &self,
+        queue_name: Q,
+        states: S,
+    ) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+        S: IntoIterator<Item = JobState>,
+    {
+        let states: Vec<String> = states.into_iter().map(|s| s.to_string()).collect();
+        let count: (i64,) = sqlx::query_as(&self.stmt.purge_queue)
+            .bind(queue_name.as_ref())
+            .bind(states)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0 as usize)
+    }
+
+    /// Number of jobs in the queue in states preceding `before_state`.
+    ///
+    /// E.g. with [`JobState::Active`] (which is what PgBoss uses by default),
+    /// the jobs waiting to be processed are counted, i.e. those in `created`
+    /// (including deferred ones) or `retry` state. Will return `0` if the queue
+    /// does not exist.
+    pub async fn get_queue_size<Q>(
+        &self,
+        queue_name: Q,
+        before_state: JobState,
+    ) -> Result<usize, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let count: (i64,) = sqlx::query_as(&self.stmt.get_queue_size)
+            .bind(queue_name.as_ref())
+            .bind(before_state.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count.0 as usize)
+    }
+
+    /// Returns [`QueueStats`] on the queue with this name, if any.
+    pub async fn get_queue_stats<Q>(&self, queue_name: Q) -> Result<Option<QueueStats>, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let stats: Option<QueueStats> = sqlx::query_as(&self.stmt.get_queue_stats)
+            .bind(Some(queue_name.as_ref()))
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(stats)
+    }
+
+    /// Returns [`QueueStats`] on all the queues in the system.
+    pub async fn get_all_queue_stats(&self) -> Result<Vec<QueueStats>, Error> {
+        let stats: Vec<QueueStats> = sqlx::query_as(&self.stmt.get_queue_stats)
+            .bind(None::<&str>)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(stats)
+    }
 }
@@ -0,0 +1,171 @@
+use super::Client;
+use crate::schedule::CronSchedule;
+use crate::{Error, Schedule, ScheduleDetails, Scheduler};
+use sqlx::types::Json;
+use sqlx::Acquire;
+use std::borrow::Borrow;
+use std::time::Duration;
+
+impl Client {
+    /// Start sending jobs to a queue on schedule.
+    ///
+    /// There can only be one schedule per queue, so this will replace
+    /// the queue's schedule, if any. Jobs are only sent while there is at least
+    /// one [`Scheduler`] running (see [`Client::scheduler`]).
+    ///
+    /// Will return [`Error::InvalidSchedule`] if the cron expression or the time zone
+    /// cannot be parsed, and [`Error::DoesNotExist`] if the queue does not exist.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use pgboss::{Client, Job, Schedule};
+    /// use serde_json::json;
+    ///
+    /// let c = Client::connect().await.unwrap();
+    /// let job = Job::builder()
+    ///     .queue_name("reports")
+    ///     .data(json!({"report": "daily"}))
+    ///     .build();
+    /// let schedule = Schedule::builder()
+    ///     .cron("0 8 * * *")
+    ///     .timezone("Europe/Berlin")
+    ///     .job(job)
+    ///     .build();
+    /// c.schedule(&schedule).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn schedule<'a, S>(&self, schedule: S) -> Result<(), Error>
+    where
+        S: Borrow<Schedule<'a>>,
+    {
+        let schedule = schedule.borrow();
+        CronSchedule::parse(schedule.cron, schedule.timezone)?;
+        let job = &schedule.job;
+        sqlx::query(&self.stmt.upsert_schedule)
+            .bind(job.queue_name)
+            .bind(schedule.cron)
+            .bind(schedule.timezone)
+            .bind(Json(&job.data))
+            .bind(Json(job.opts().for_schedule()))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                if let Some(db_error) = e.as_database_error() {
+                    if db_error.constraint() == Some("schedule_name_fkey") {
+                        return Error::DoesNotExist {
+                            msg: "queue does not exist",
+                        };
+                    }
+                }
+                Error::Sqlx(e)
+            })?;
+        Ok(())
+    }
+
+    /// Stop sending jobs to this queue on schedule.
+    ///
+    /// Returns `false` if there was no schedule for this queue.
+    pub async fn unschedule<Q>(&self, queue_name: Q) -> Result<bool, Error>
+    where
+        Q: AsRef<str>,
+    {
+        let result = sqlx::query(&self.stmt.delete_schedule)
+            .bind(queue_name.as_ref())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Return info on all the schedules in the system.
+    pub async fn get_schedules(&self) -> Result<Vec<ScheduleDetails>, Error> {
+        let schedules: Vec<ScheduleDetails> = sqlx::query_as(&self.stmt.get_schedules)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(schedules)
+    }
+
+    /// Start sending jobs on schedules in the background.
+    ///
+    /// The scheduler will check the schedules every `interval`, which should be well
+    /// below a minute, e.g. `30` seconds. See [`Scheduler`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if zero duration is given.
+    pub fn scheduler(&self, interval: Duration) -> Scheduler {
+        assert!(!interval.is_zero(), "interval should be greater than zero");
+        Scheduler::start(self.clone(), interval)
+    }
+
+    /// Send a job for each schedule that has fired since the previous check.
+    ///
+    /// Returns `None` if another process is checking the schedules at the moment,
+    /// otherwise returns the number of jobs sent.
+    pub(crate) async fn send_scheduled_jobs(&self) -> Result<Option<usize>, Error> {
+        let mut tx = self.pool.begin().await?;
+        let locked: bool = sqlx::query_scalar(&self.stmt.try_lock_cron)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !locked {
+            return Ok(None);
+        }
+        let (cron_on, now): (Option<_>, _) = sqlx::query_as(&self.stmt.get_cron_on)
+            .fetch_one(&mut *tx)
+            .await?;
+        // another process, whose transaction started later than this one's, got the lock first
+        // and has already covered this window
+        if cron_on.is_some_and(|cron_on| now <= cron_on) {
+            return Ok(Some(0));
+        }
+        let schedules: Vec<ScheduleDetails> = sqlx::query_as(&self.stmt.get_schedules)
+            .fetch_all(&mut *tx)
+            .await?;
+        let mut sent = 0;
+        for schedule in schedules {
+            let cron = match CronSchedule::parse(&schedule.cron, schedule.timezone.as_deref()) {
+                Ok(cron) => cron,
+                Err(e) => {
+                    log::error!("Skipping schedule for queue {}: {}", schedule.queue_name, e);
+                    continue;
+                }
+            };
+            // missed ticks are collapsed into one, and nothing is sent on the very first check
+            if !cron.fires_between(cron_on.unwrap_or(now), now) {
+                continue;
+            }
+            let mut options = schedule.options.unwrap_or_else(|| serde_json::json!({}));
+            if self.opts.notify {
+                options["notify"] = true.into();
+            }
+            // a failing job, e.g. a throttled one, should not abort the entire transaction
+            let mut savepoint = (&mut *tx).begin().await?;
+            let created: Result<Option<uuid::Uuid>, _> = sqlx::query_scalar(&self.stmt.create_job)
+                .bind(None::<uuid::Uuid>)
+                .bind(&schedule.queue_name)
+                .bind(Json(&schedule.data))
+                .bind(Json(&options))
+                .fetch_one(&mut *savepoint)
+                .await;
+            match created {
+                Ok(Some(_)) => {
+                    savepoint.commit().await?;
+                    sent += 1;
+                }
+                Ok(None) => savepoint.commit().await?,
+                Err(e) => {
+                    log::error!(
+                        "Failed to send scheduled job to queue {}: {:?}",
+                        schedule.queue_name,
+                        e
+                    );
+                    savepoint.rollback().await?;
+                }
+            }
+        }
+        sqlx::query(&self.stmt.set_cron_on)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some(sent))
+    }
+}
@@ -1,4 +1,5 @@
 use thiserror::Error;
+use uuid::Uuid;
 
 /// Enumerates all errors that this crate may return.
 #[derive(Debug, Error)]
@@ -31,4 +32,27 @@ pub enum Error {
         /// Which throttling has been applied.
         msg: &'static str,
     },
+
+    /// Job's payload cannot be deserialized into the requested type.
+    ///
+    /// If the job has been fetched, it is `active` nevertheless,
+    /// so you will most likely want to fail it.
+    #[error("failed to decode payload of job {job_id} from queue {queue_name}: {source}")]
+    Payload {
+        /// ID of the job.
+        job_id: Uuid,
+
+        /// Name of the queue the job belongs to.
+        queue_name: String,
+
+        /// Underlying deserialization error.
+        source: serde_json::Error,
+    },
+
+    /// Cron expression or time zone of a schedule cannot be parsed.
+    #[error("invalid schedule: {msg}")]
+    InvalidSchedule {
+        /// Details on what exactly is wrong.
+        msg: String,
+    },
 }
@@ -0,0 +1,52 @@
+use crate::utils::AbortOnDrop;
+use crate::Client;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Keeps touching an `active` job in the background until dropped.
+///
+/// Use [`Client::heartbeat`] to get an instance.
+#[derive(Debug)]
+pub struct Heartbeat {
+    job_id: Uuid,
+    _task: AbortOnDrop,
+}
+
+impl Heartbeat {
+    pub(crate) fn start(
+        client: Client,
+        queue_name: String,
+        job_id: Uuid,
+        interval: Duration,
+    ) -> Self {
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match client.touch_job(&queue_name, job_id).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        // the job has been completed, failed, expired, etc.
+                        return;
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "Failed to touch job {} from queue {}: {:?}",
+                            job_id,
+                            queue_name,
+                            e
+                        );
+                    }
+                }
+            }
+        });
+        Heartbeat {
+            job_id,
+            _task: AbortOnDrop(task.abort_handle()),
+        }
+    }
+
+    /// ID of the job being touched.
+    pub fn job_id(&self) -> Uuid {
+        self.job_id
+    }
+}
@@ -1,6 +1,6 @@
 use super::utils;
 use chrono::{DateTime, NaiveDateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sqlx::{
     postgres::{PgRow, PgValueRef},
     prelude::FromRow,
@@ -12,7 +12,7 @@ use uuid::Uuid;
 #[cfg(doc)]
 use crate::{Client, Queue};
 
-use crate::QueuePolicy;
+use crate::{Backoff, Error, QueuePolicy};
 
 /// Job's state.
 ///
@@ -69,6 +69,23 @@ impl std::fmt::Display for JobState {
     }
 }
 
+/// What to do with a job when one of the jobs it depends on fails or is cancelled.
+///
+/// See [`Job::parents`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum OnParentFailure {
+    /// Mark the job as `cancelled` (default).
+    #[default]
+    Cancel,
+
+    /// Mark the job as `failed`.
+    ///
+    /// Note that the job is not retried and not sent to its dead letter queue.
+    Fail,
+}
+
 /// Custom job options.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -91,6 +108,9 @@ pub(crate) struct JobOptions<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     retry_backoff: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backoff: Option<Backoff>,
+
     #[serde(
         serialize_with = "utils::serialize_duration_as_secs",
         skip_serializing_if = "Option::is_none"
@@ -107,10 +127,41 @@ pub(crate) struct JobOptions<'a> {
         serialize_with = "utils::serialize_duration_as_secs",
         skip_serializing_if = "Option::is_none"
     )]
-    singleton_for: Option<Duration>,
+    pub(crate) singleton_for: Option<Duration>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    singleton_key: Option<&'a str>,
+    pub(crate) singleton_key: Option<&'a str>,
+
+    /// Shift of the singleton slot, used to put a job into the next slot.
+    ///
+    /// Unless `start_after` is given, the job will start when the shifted slot begins.
+    #[serde(
+        serialize_with = "utils::serialize_duration_as_secs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) singleton_offset: Option<Duration>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    parents: Vec<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on_parent_failure: Option<OnParentFailure>,
+
+    /// Whether to notify consumers listening on the queue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) notify: Option<bool>,
+}
+
+impl JobOptions<'_> {
+    /// Options to be stored with a schedule and used for every job sent on it.
+    ///
+    /// Absolute timestamps cannot be shared by jobs created at different
+    /// points in time, so these are dropped.
+    pub(crate) fn for_schedule(mut self) -> Self {
+        self.keep_until = None;
+        self.start_after = None;
+        self
+    }
 }
 
 /// A job to be sent to the server.
@@ -148,6 +199,12 @@ pub struct Job<'a> {
     /// Whether to use a backoff between retry attempts.
     pub retry_backoff: Option<bool>,
 
+    /// Strategy to calculate the time to wait before a retry attempt with.
+    ///
+    /// Takes precedence over [`Job::retry_backoff`]. If neither is set, the
+    /// strategy of the queue is used, see [`Queue::backoff`].
+    pub backoff: Option<Backoff>,
+
     /// Time to wait before expiring this job.
     ///
     /// Specifies for how long this job may be in `active` state before
@@ -180,15 +237,30 @@ pub struct Job<'a> {
     ///
     /// Will extend throttling to allow one job per key within the time slot.
     pub singleton_key: Option<&'a str>,
+
+    /// IDs of the jobs this job depends on.
+    ///
+    /// The job will not be fetched until all of these jobs are `completed` (parents which
+    /// have been archived since count as well). Should any of them fail or be cancelled,
+    /// the job is either cancelled or failed as per [`Job::on_parent_failure`], and so are
+    /// the jobs depending on it in their turn. Note that the job will wait forever for
+    /// a parent which is deleted before it completes.
+    pub parents: Vec<Uuid>,
+
+    /// What to do with this job when one of its [`Job::parents`] fails.
+    pub on_parent_failure: OnParentFailure,
 }
 
 /// A job fetched from the server.
 ///
 /// As soon as a job is fetched from the server, it's status transitions to `active`
 /// and whoever has fetch this job will hav
+///
+/// The job's payload is a raw JSON value by default. Use [`JobDetails::try_into_typed`]
+/// (or [`Client::fetch_typed_job`]) to get the payload deserialized into your own type.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[non_exhaustive]
-pub struct JobDetails {
+pub struct JobDetails<T = serde_json::Value> {
     /// ID of this job.
     pub id: Uuid,
 
@@ -196,7 +268,7 @@ pub struct JobDetails {
     pub queue_name: String,
 
     /// Job's payload.
-    pub data: serde_json::Value,
+    pub data: T,
 
     /// Execution timeout.
     ///
@@ -228,13 +300,17 @@ pub struct JobDetails {
     /// Whether to use a backoff between retry attempts.
     pub retry_backoff: bool,
 
+    /// Strategy used to calculate the time to wait before a retry attempt.
+    pub backoff: Backoff,
+
     /// When the job was registered by the server.
     pub created_at: DateTime<Utc>,
 
     /// When to make this job 'visible' for consumers.
     pub start_after: DateTime<Utc>,
 
-    /// When this job was last consumed.
+    /// When this job was last consumed or, if it has been touched since then,
+    /// last touched (see [`Client::touch_jobs`]).
     ///
     /// Will be `None` for a job that was not consumed just yet.
     pub started_at: Option<DateTime<Utc>>,
@@ -267,8 +343,14 @@ pub struct JobDetails {
     /// Job's output, if any.
     ///
     /// A worker can report `output` when completing ([`Client::complete_job`] and [`Client::complete_jobs`])
-    /// or failing ([`Client::fail_job`] and [`Client::fail_jobs`]) a job.
+    /// or failing ([`Client::fail_job`] and [`Client::fail_jobs`]) a job, as well as
+    /// report progress while the job is still `active` ([`Client::report_progress`]).
     pub output: Option<serde_json::Value>,
+
+    /// IDs of the jobs this job depends on, if any.
+    ///
+    /// Use [`Client::get_child_jobs`] to list jobs depending on this job.
+    pub parents: Vec<Uuid>,
 }
 
 impl FromRow<'_, PgRow> for JobDetails {
@@ -325,6 +407,14 @@ impl FromRow<'_, PgRow> for JobDetails {
             }),
         })?;
         let retry_backoff: bool = row.try_get("retry_backoff")?;
+        // not stored for jobs created before the strategy could be specified
+        let backoff = row
+            .try_get::<Option<sqlx::types::Json<Backoff>>, _>("backoff")?
+            .map(|v| v.0)
+            .unwrap_or(match retry_backoff {
+                true => Backoff::Exponential,
+                false => Backoff::Fixed,
+            });
         let created_at: DateTime<Utc> = row.try_get("created_at")?;
         let started_at: Option<DateTime<Utc>> = row.try_get("started_at")?;
         let completed_at: Option<DateTime<Utc>> = row.try_get("completed_at")?;
@@ -345,6 +435,7 @@ impl FromRow<'_, PgRow> for JobDetails {
         })?;
         let keep_until: DateTime<Utc> = row.try_get("keep_until")?;
         let output: Option<serde_json::Value> = row.try_get("output")?;
+        let parents: Option<Vec<Uuid>> = row.try_get("parents")?;
 
         Ok(JobDetails {
             id,
@@ -358,6 +449,7 @@ impl FromRow<'_, PgRow> for JobDetails {
             retry_delay,
             retry_count,
             retry_backoff,
+            backoff,
             created_at,
             start_after,
             started_at,
@@ -367,6 +459,49 @@ impl FromRow<'_, PgRow> for JobDetails {
             completed_at,
             keep_until,
             output,
+            parents: parents.unwrap_or_default(),
+        })
+    }
+}
+
+impl JobDetails {
+    /// Deserialize this job's payload into `T`.
+    ///
+    /// Will return [`Error::Payload`] if the payload cannot be deserialized,
+    /// so that the job can be failed (see [`Client::fail_job_with_details`])
+    /// with the error message as details.
+    pub fn try_into_typed<T>(self) -> Result<JobDetails<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let data = serde_json::from_value(self.data).map_err(|e| Error::Payload {
+            job_id: self.id,
+            queue_name: self.queue_name.clone(),
+            source: e,
+        })?;
+        Ok(JobDetails {
+            id: self.id,
+            queue_name: self.queue_name,
+            data,
+            expire_in: self.expire_in,
+            state: self.state,
+            policy: self.policy,
+            priority: self.priority,
+            retry_limit: self.retry_limit,
+            retry_delay: self.retry_delay,
+            retry_count: self.retry_count,
+            retry_backoff: self.retry_backoff,
+            backoff: self.backoff,
+            created_at: self.created_at,
+            start_after: self.start_after,
+            started_at: self.started_at,
+            singleton_at: self.singleton_at,
+            singleton_key: self.singleton_key,
+            completed_at: self.completed_at,
+            dead_letter: self.dead_letter,
+            keep_until: self.keep_until,
+            output: self.output,
+            parents: self.parents,
         })
     }
 }
@@ -384,11 +519,16 @@ impl<'a> Job<'a> {
             retry_limit: self.retry_limit,
             retry_delay: self.retry_delay,
             retry_backoff: self.retry_backoff,
+            backoff: self.backoff,
             expire_in: self.expire_in,
             keep_until: self.keep_until,
             start_after: self.start_after,
             singleton_for: self.singleton_for,
             singleton_key: self.singleton_key,
+            singleton_offset: None,
+            parents: self.parents.clone(),
+            on_parent_failure: (!self.parents.is_empty()).then_some(self.on_parent_failure),
+            notify: None,
         }
     }
 }
@@ -405,11 +545,14 @@ pub struct JobBuilder<'a> {
     pub(crate) retry_limit: Option<usize>,
     pub(crate) retry_delay: Option<Duration>,
     pub(crate) retry_backoff: Option<bool>,
+    pub(crate) backoff: Option<Backoff>,
     pub(crate) expire_in: Option<Duration>,
     pub(crate) keep_until: Option<DateTime<Utc>>,
     pub(crate) start_after: Option<DateTime<Utc>>,
     pub(crate) singleton_for: Option<Duration>,
     pub(crate) singleton_key: Option<&'a str>,
+    pub(crate) parents: Vec<Uuid>,
+    pub(crate) on_parent_failure: OnParentFailure,
 }
 
 impl<'a> JobBuilder<'a> {
@@ -461,6 +604,14 @@ impl<'a> JobBuilder<'a> {
         self
     }
 
+    /// Strategy to calculate the time to wait before a retry attempt with.
+    ///
+    /// Takes precedence over [`JobBuilder::retry_backoff`].
+    pub fn backoff(mut self, value: Backoff) -> Self {
+        self.backoff = Some(value);
+        self
+    }
+
     /// Time to wait before expiring this job.
     ///
     /// Should be between 1 second and 24 hours, or simply unset (default).
@@ -514,6 +665,20 @@ impl<'a> JobBuilder<'a> {
         self
     }
 
+    /// ID of a job this job depends on.
+    ///
+    /// Can be called multiple times to make this job wait for all the given jobs.
+    pub fn depends_on(mut self, value: Uuid) -> Self {
+        self.parents.push(value);
+        self
+    }
+
+    /// What to do with this job when one of the jobs it depends on fails.
+    pub fn on_parent_failure(mut self, value: OnParentFailure) -> Self {
+        self.on_parent_failure = value;
+        self
+    }
+
     /// Creates a job.
     pub fn build(self) -> Job<'a> {
         Job {
@@ -525,11 +690,50 @@ impl<'a> JobBuilder<'a> {
             retry_limit: self.retry_limit,
             retry_delay: self.retry_delay,
             retry_backoff: self.retry_backoff,
+            backoff: self.backoff,
             expire_in: self.expire_in,
             keep_until: self.keep_until,
             start_after: self.start_after,
             singleton_for: self.singleton_for,
             singleton_key: self.singleton_key,
+            parents: self.parents,
+            on_parent_failure: self.on_parent_failure,
+        }
+    }
+}
+
+/// Options for retrying jobs on demand, see [`Client::retry_jobs_with`](crate::Client::retry_jobs_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryOptions {
+    pub(crate) include_completed: bool,
+    pub(crate) reset_retry_count: bool,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        RetryOptions {
+            include_completed: false,
+            reset_retry_count: true,
         }
     }
 }
+
+impl RetryOptions {
+    /// Whether to also retry `completed` jobs, and not only `failed` ones.
+    ///
+    /// Defaults to `false`.
+    pub fn include_completed(mut self, value: bool) -> Self {
+        self.include_completed = value;
+        self
+    }
+
+    /// Whether to start counting retries from scratch.
+    ///
+    /// If set to `false`, the job's `retry_count` is preserved, and so a job
+    /// which has already exhausted its retry limit will get one more attempt only.
+    /// Defaults to `true`.
+    pub fn reset_retry_count(mut self, value: bool) -> Self {
+        self.reset_retry_count = value;
+        self
+    }
+}
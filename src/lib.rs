@@ -52,17 +52,35 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod backoff;
 mod client;
 mod error;
+mod heartbeat;
 mod job;
+mod listener;
+mod maintenance;
+mod query;
 mod queue;
+mod schedule;
+mod scheduler;
 mod sql;
+mod supervisor;
 mod utils;
+mod worker;
 
+pub use backoff::Backoff;
 pub use client::{Client, ClientBuilder};
 pub use error::Error;
-pub use job::{Job, JobBuilder, JobDetails, JobState};
-pub use queue::{Queue, QueueBuilder, QueueDetails, QueuePolicy};
+pub use heartbeat::Heartbeat;
+pub use job::{Job, JobBuilder, JobDetails, JobState, OnParentFailure, RetryOptions};
+pub use listener::Listener;
+pub use maintenance::{MaintenanceDetails, MaintenanceOutcome, MaintenanceRun};
+pub use query::{JobCursor, JobQuery};
+pub use queue::{Queue, QueueBuilder, QueueDetails, QueuePolicy, QueueStats, QueueUpdate};
+pub use schedule::{Schedule, ScheduleBuilder, ScheduleDetails};
+pub use scheduler::Scheduler;
+pub use supervisor::{Supervisor, SupervisorBuilder};
+pub use worker::{StopDetails, Worker, WorkerBuilder};
 
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
@@ -0,0 +1,186 @@
+use crate::utils::AbortOnDrop;
+use crate::Error;
+use sqlx::postgres::{PgListener, PgPool, PgPoolOptions};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+#[cfg(doc)]
+use crate::{Client, ClientBuilder};
+
+/// Wakes up consumers as soon as a job becomes available in the queues being listened on.
+///
+/// Use [`Client::listen`] to get an instance. Note that only jobs sent by clients
+/// with [`ClientBuilder::notify`] enabled and visible to consumers right away are
+/// reported, so you will still want to poll the queues from time to time,
+/// e.g. to pick up deferred or retried jobs.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use std::time::Duration;
+/// use pgboss::Client;
+///
+/// let c = Client::builder().notify(true).connect().await.unwrap();
+/// let listener = c.listen(["qname"]).await.unwrap();
+/// loop {
+///     match c.fetch_job("qname").await.unwrap() {
+///         Some(job) => { /* process the job */ }
+///         None => {
+///             listener.wait(Duration::from_secs(5)).await;
+///         }
+///     }
+/// }
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct Listener {
+    notify: Arc<Notify>,
+    _task: AbortOnDrop,
+}
+
+impl Listener {
+    pub(crate) async fn connect(pool: &PgPool, channels: Vec<String>) -> Result<Self, Error> {
+        let mut listener = connect_dedicated(pool).await?;
+        listener
+            .listen_all(channels.iter().map(String::as_str))
+            .await?;
+        let notify = Arc::new(Notify::new());
+        let task = tokio::spawn(Self::recv(listener, notify.clone()));
+        Ok(Listener {
+            notify,
+            _task: AbortOnDrop(task.abort_handle()),
+        })
+    }
+
+    async fn recv(mut listener: PgListener, notify: Arc<Notify>) {
+        loop {
+            match listener.recv().await {
+                Ok(_) => notify.notify_one(),
+                Err(e) => {
+                    // notifications may have been lost while reconnecting,
+                    // so waking up consumers to make them check the queues
+                    log::error!("Failed to receive notification: {:?}", e);
+                    notify.notify_one();
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    /// Wait for a job to become available or for the `timeout` to elapse.
+    ///
+    /// Returns `true` if a notification has been received, and `false` on timeout.
+    /// A notification received while no one was waiting is not lost,
+    /// rather the next call to this method will return right away.
+    pub async fn wait(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, self.notify.notified())
+            .await
+            .is_ok()
+    }
+}
+
+type Waiters = Arc<Mutex<HashMap<Uuid, Vec<Arc<Notify>>>>>;
+
+/// Wakes up those waiting for jobs to be done, see [`Client::wait_for_job`].
+///
+/// Shared by all the clones of a client, so that there is one connection listening
+/// on the schema's done channel no matter how many jobs are being waited for.
+#[derive(Debug)]
+pub(crate) struct DoneListener {
+    waiters: Waiters,
+    _task: AbortOnDrop,
+}
+
+impl DoneListener {
+    pub(crate) async fn connect(pool: &PgPool, channel: &str) -> Result<Self, Error> {
+        let mut listener = connect_dedicated(pool).await?;
+        listener.listen(channel).await?;
+        let waiters = Waiters::default();
+        let task = tokio::spawn(Self::recv(listener, waiters.clone()));
+        Ok(DoneListener {
+            waiters,
+            _task: AbortOnDrop(task.abort_handle()),
+        })
+    }
+
+    async fn recv(mut listener: PgListener, waiters: Waiters) {
+        loop {
+            match listener.recv().await {
+                Ok(notification) => {
+                    let Ok(job_id) = notification.payload().parse::<Uuid>() else {
+                        continue;
+                    };
+                    if let Some(notifies) = waiters.lock().unwrap().get(&job_id) {
+                        notifies.iter().for_each(|n| n.notify_one());
+                    }
+                }
+                Err(e) => {
+                    // notifications may have been lost while reconnecting,
+                    // so making everyone check on their jobs
+                    log::error!("Failed to receive notification: {:?}", e);
+                    for notifies in waiters.lock().unwrap().values() {
+                        notifies.iter().for_each(|n| n.notify_one());
+                    }
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+
+    /// Start waiting for the job with this ID.
+    ///
+    /// A notification received before [`DoneWaiter::wait`] is called is not lost.
+    pub(crate) fn waiter(&self, job_id: Uuid) -> DoneWaiter {
+        let notify = Arc::new(Notify::new());
+        self.waiters
+            .lock()
+            .unwrap()
+            .entry(job_id)
+            .or_default()
+            .push(notify.clone());
+        DoneWaiter {
+            job_id,
+            notify,
+            waiters: self.waiters.clone(),
+        }
+    }
+}
+
+/// Registration of a wait for a job, removed when dropped.
+pub(crate) struct DoneWaiter {
+    job_id: Uuid,
+    notify: Arc<Notify>,
+    waiters: Waiters,
+}
+
+impl DoneWaiter {
+    /// Wait for a notification on the job or for the `timeout` to elapse.
+    pub(crate) async fn wait(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, self.notify.notified())
+            .await
+            .is_ok()
+    }
+}
+
+impl Drop for DoneWaiter {
+    fn drop(&mut self) {
+        let mut waiters = self.waiters.lock().unwrap();
+        if let Some(notifies) = waiters.get_mut(&self.job_id) {
+            notifies.retain(|n| !Arc::ptr_eq(n, &self.notify));
+            if notifies.is_empty() {
+                waiters.remove(&self.job_id);
+            }
+        }
+    }
+}
+
+/// Connects a [`PgListener`] using the connect options of the `pool`, but not its connections,
+/// since the listener holds on to its connection for as long as it lives.
+pub(crate) async fn connect_dedicated(pool: &PgPool) -> Result<PgListener, Error> {
+    let dedicated = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with((*pool.connect_options()).clone());
+    Ok(PgListener::connect_with(&dedicated).await?)
+}
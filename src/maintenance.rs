@@ -0,0 +1,46 @@
+use crate::Error;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Outcome of a maintenance run (see [`Client::maintain`](crate::Client::maintain)).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MaintenanceDetails {
+    /// Number of `active` jobs that have been failed because of expiration.
+    pub expired_jobs: usize,
+
+    /// Number of jobs moved to the archive.
+    pub archived_jobs: usize,
+
+    /// Number of jobs permanently deleted from the archive.
+    pub deleted_jobs: usize,
+}
+
+/// What came out of a [`Supervisor`](crate::Supervisor)'s attempt to maintain the system.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MaintenanceOutcome {
+    /// This instance has performed maintenance.
+    Performed(MaintenanceDetails),
+
+    /// Maintenance has been skipped, since it is being or has recently been
+    /// performed by another instance.
+    Skipped,
+
+    /// Maintenance failed and will be attempted again on the next run.
+    Failed(Arc<Error>),
+}
+
+/// Details on a [`Supervisor`](crate::Supervisor)'s run.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct MaintenanceRun {
+    /// When this run started.
+    pub started_at: DateTime<Utc>,
+
+    /// When this run finished.
+    pub finished_at: DateTime<Utc>,
+
+    /// What came out of this run.
+    pub outcome: MaintenanceOutcome,
+}
@@ -0,0 +1,174 @@
+use crate::{Client, Error, JobDetails, JobState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Position in a listing of jobs to continue from.
+///
+/// Jobs are listed in the order they have been created in, and the
+/// cursor points at the last job of the previous page, see [`JobQuery::after`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct JobCursor {
+    /// When the job has been created.
+    pub created_at: DateTime<Utc>,
+
+    /// ID of the job.
+    pub id: Uuid,
+}
+
+impl JobCursor {
+    /// Creates a cursor pointing at the job with these creation time and ID.
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        JobCursor { created_at, id }
+    }
+}
+
+impl<T> From<&JobDetails<T>> for JobCursor {
+    fn from(job: &JobDetails<T>) -> Self {
+        JobCursor::new(job.created_at, job.id)
+    }
+}
+
+/// Query to list jobs with.
+///
+/// Use [`Client::query_jobs`] to get an instance. All the filters are optional
+/// and are combined with each other, so that only jobs matching all of them are listed.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// # use pgboss::{Client, JobState};
+/// # use serde_json::json;
+/// # let c = Client::connect().await.unwrap();
+/// let mut query = c
+///     .query_jobs()
+///     .queue_name("emails")
+///     .state(JobState::Failed)
+///     .data_contains(json!({"tenant": "acme"}))
+///     .limit(50);
+/// loop {
+///     let jobs = query.fetch().await.unwrap();
+///     let Some(last) = jobs.last() else {
+///         break;
+///     };
+///     query = query.after(last);
+/// }
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct JobQuery {
+    client: Client,
+    pub(crate) queue_name: Option<String>,
+    pub(crate) states: Vec<JobState>,
+    pub(crate) created_after: Option<DateTime<Utc>>,
+    pub(crate) created_before: Option<DateTime<Utc>>,
+    pub(crate) singleton_key: Option<String>,
+    pub(crate) dead_letter: Option<String>,
+    pub(crate) data_contains: Option<serde_json::Value>,
+    pub(crate) after: Option<JobCursor>,
+    pub(crate) limit: usize,
+}
+
+impl JobQuery {
+    pub(crate) fn new(client: Client) -> Self {
+        JobQuery {
+            client,
+            queue_name: None,
+            states: Vec::new(),
+            created_after: None,
+            created_before: None,
+            singleton_key: None,
+            dead_letter: None,
+            data_contains: None,
+            after: None,
+            limit: 100,
+        }
+    }
+
+    /// Only list jobs from the queue with this name.
+    pub fn queue_name<Q>(mut self, value: Q) -> Self
+    where
+        Q: Into<String>,
+    {
+        self.queue_name = Some(value.into());
+        self
+    }
+
+    /// Only list jobs in this state.
+    ///
+    /// Can be called multiple times to list jobs in any of the given states.
+    pub fn state(mut self, value: JobState) -> Self {
+        self.states.push(value);
+        self
+    }
+
+    /// Only list jobs created at or after this moment.
+    pub fn created_after(mut self, value: DateTime<Utc>) -> Self {
+        self.created_after = Some(value);
+        self
+    }
+
+    /// Only list jobs created before this moment.
+    pub fn created_before(mut self, value: DateTime<Utc>) -> Self {
+        self.created_before = Some(value);
+        self
+    }
+
+    /// Only list jobs with this singleton key.
+    pub fn singleton_key<K>(mut self, value: K) -> Self
+    where
+        K: Into<String>,
+    {
+        self.singleton_key = Some(value.into());
+        self
+    }
+
+    /// Only list jobs with this dead letter queue.
+    pub fn dead_letter<D>(mut self, value: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.dead_letter = Some(value.into());
+        self
+    }
+
+    /// Only list jobs whose payload contains this JSON value.
+    ///
+    /// Uses PostgreSQL's `@>` operator, so e.g. `{"tenant": "acme"}` will match
+    /// `{"tenant": "acme", "user": 1}`, but not `{"tenant": "umbrella"}`.
+    pub fn data_contains<D>(mut self, value: D) -> Self
+    where
+        D: Into<serde_json::Value>,
+    {
+        self.data_contains = Some(value.into());
+        self
+    }
+
+    /// Only list jobs following this one.
+    ///
+    /// Pass the last job of the current page (or a [`JobCursor`] pointing at it)
+    /// to get the next page.
+    pub fn after<C>(mut self, value: C) -> Self
+    where
+        C: Into<JobCursor>,
+    {
+        self.after = Some(value.into());
+        self
+    }
+
+    /// Maximum number of jobs to list at once.
+    ///
+    /// Defaults to `100`.
+    pub fn limit(mut self, value: usize) -> Self {
+        self.limit = value;
+        self
+    }
+
+    /// Fetch a page of jobs matching this query.
+    ///
+    /// Jobs are ordered by their creation time (and ID, for jobs created at the same moment).
+    /// Note that only jobs which have not been archived yet are listed.
+    pub async fn fetch(&self) -> Result<Vec<JobDetails>, Error> {
+        self.client.fetch_jobs_page(self).await
+    }
+}
@@ -1,4 +1,5 @@
 use super::utils;
+use crate::Backoff;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{postgres::PgRow, FromRow, Row};
@@ -92,6 +93,13 @@ pub struct Queue<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_backoff: Option<bool>,
 
+    /// Strategy to calculate the time to wait before a retry attempt with.
+    ///
+    /// Takes precedence over [`Queue::retry_backoff`], but not over the settings
+    /// of individual jobs, see [`Job::backoff`](crate::Job::backoff).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<Backoff>,
+
     /// Time to wait before expiring this job.
     ///
     /// Should be between 1 second and 24 hours, or simply unset (default).
@@ -129,6 +137,7 @@ pub struct QueueBuilder<'a> {
     retry_limit: Option<usize>,
     retry_delay: Option<Duration>,
     retry_backoff: Option<bool>,
+    backoff: Option<Backoff>,
     expire_in: Option<Duration>,
     retain_for: Option<Duration>,
 }
@@ -173,6 +182,12 @@ impl<'a> QueueBuilder<'a> {
         self
     }
 
+    /// Strategy to calculate the time to wait before a retry attempt with.
+    pub fn backoff(mut self, val: Backoff) -> Self {
+        self.backoff = Some(val);
+        self
+    }
+
     /// Time to wait before expiring this job.
     ///
     /// Should be between 1 second and 24 hours, or simply unset (default)
@@ -198,6 +213,7 @@ impl<'a> QueueBuilder<'a> {
             retry_limit: self.retry_limit,
             retry_delay: self.retry_delay,
             retry_backoff: self.retry_backoff,
+            backoff: self.backoff,
             expire_in: self.expire_in,
             retain_for: self.retain_for,
         }
@@ -223,6 +239,9 @@ pub struct QueueDetails {
     /// Whether to use a backoff between retry attempts.
     pub retry_backoff: Option<bool>,
 
+    /// Strategy to calculate the time to wait before a retry attempt with.
+    pub backoff: Option<Backoff>,
+
     /// Time to wait before expiring this job.
     pub expire_in: Option<Duration>,
 
@@ -232,6 +251,11 @@ pub struct QueueDetails {
     /// Name of the dead letter queue.
     pub dead_letter: Option<String>,
 
+    /// Whether consumption of this queue has been paused.
+    ///
+    /// See [`Client::pause_queue`](crate::Client::pause_queue).
+    pub paused: bool,
+
     /// Date and time when this queue was created.
     pub created_at: DateTime<Utc>,
 
@@ -269,6 +293,9 @@ impl FromRow<'_, PgRow> for QueueDetails {
                     }),
                 })?;
         let retry_backoff: Option<bool> = row.try_get("retry_backoff")?;
+        let backoff = row
+            .try_get::<Option<sqlx::types::Json<Backoff>>, _>("backoff")?
+            .map(|v| v.0);
         let expire_in: Option<Duration> =
             row.try_get("expire_seconds")
                 .and_then(|v: Option<i32>| match v {
@@ -292,6 +319,7 @@ impl FromRow<'_, PgRow> for QueueDetails {
                     }),
                 })?;
         let dead_letter: Option<String> = row.try_get("dead_letter")?;
+        let paused: bool = row.try_get("paused")?;
         let created_at: DateTime<Utc> = row.try_get("created_at")?;
         let updated_at: DateTime<Utc> = row.try_get("updated_at")?;
         Ok(QueueDetails {
@@ -300,11 +328,102 @@ impl FromRow<'_, PgRow> for QueueDetails {
             retry_limit,
             retry_delay,
             retry_backoff,
+            backoff,
             expire_in,
             retain_for,
             dead_letter,
+            paused,
             created_at,
             updated_at,
         })
     }
 }
+
+/// Outcome of a queue update.
+///
+/// See [`Client::update_queue`](crate::Client::update_queue).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct QueueUpdate {
+    /// The queue's configuration prior to the update.
+    pub before: QueueDetails,
+
+    /// The queue's configuration after the update.
+    pub after: QueueDetails,
+}
+
+impl QueueUpdate {
+    /// Names of the settings that have been changed by the update, e.g. `["retry_limit"]`.
+    pub fn changes(&self) -> Vec<&'static str> {
+        let (before, after) = (&self.before, &self.after);
+        let mut changes = Vec::new();
+        if before.policy != after.policy {
+            changes.push("policy");
+        }
+        if before.retry_limit != after.retry_limit {
+            changes.push("retry_limit");
+        }
+        if before.retry_delay != after.retry_delay {
+            changes.push("retry_delay");
+        }
+        if before.retry_backoff != after.retry_backoff {
+            changes.push("retry_backoff");
+        }
+        if before.backoff != after.backoff {
+            changes.push("backoff");
+        }
+        if before.expire_in != after.expire_in {
+            changes.push("expire_in");
+        }
+        if before.retain_for != after.retain_for {
+            changes.push("retain_for");
+        }
+        if before.dead_letter != after.dead_letter {
+            changes.push("dead_letter");
+        }
+        changes
+    }
+
+    /// Whether any of the settings has been changed by the update.
+    pub fn is_changed(&self) -> bool {
+        !self.changes().is_empty()
+    }
+}
+
+/// Number of jobs in a queue per state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+#[non_exhaustive]
+pub struct QueueStats {
+    /// Queue name.
+    pub queue_name: String,
+
+    /// Jobs in `created` state visible to consumers.
+    #[sqlx(try_from = "i64")]
+    pub created: usize,
+
+    /// Jobs in `created` state not visible to consumers just yet.
+    ///
+    /// See [`JobBuilder::start_after`](crate::JobBuilder::start_after).
+    #[sqlx(try_from = "i64")]
+    pub deferred: usize,
+
+    /// Jobs in `retry` state.
+    #[sqlx(try_from = "i64")]
+    pub retry: usize,
+
+    /// Jobs in `active` state.
+    #[sqlx(try_from = "i64")]
+    pub active: usize,
+
+    /// Jobs in `completed` state.
+    #[sqlx(try_from = "i64")]
+    pub completed: usize,
+
+    /// Jobs in `cancelled` state.
+    #[sqlx(try_from = "i64")]
+    pub cancelled: usize,
+
+    /// Jobs in `failed` state.
+    #[sqlx(try_from = "i64")]
+    pub failed: usize,
+}
@@ -0,0 +1,136 @@
+use crate::{Error, Job};
+use chrono::{DateTime, SubsecRound, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A schedule to send jobs on.
+///
+/// Jobs are created by a [`Scheduler`](crate::Scheduler) from the [`Schedule::job`] template
+/// whenever the [`Schedule::cron`] expression fires.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct Schedule<'a> {
+    /// Cron expression, e.g. `*/5 * * * *` to send a job every five minutes.
+    ///
+    /// The standard five-field syntax is supported, which is minute, hour,
+    /// day of month, month, and day of week.
+    pub cron: &'a str,
+
+    /// IANA time zone to evaluate the cron expression in, e.g. `Europe/Berlin`.
+    ///
+    /// Defaults to UTC.
+    pub timezone: Option<&'a str>,
+
+    /// Template for the jobs to be sent.
+    ///
+    /// The job will be sent to the [`Job::queue_name`] queue with [`Job::data`] as
+    /// payload. Note that [`Job::id`], [`Job::start_after`] and [`Job::keep_until`]
+    /// are not used, since these cannot be shared by multiple jobs.
+    pub job: Job<'a>,
+}
+
+impl<'a> Schedule<'a> {
+    /// Creates a builder for a schedule.
+    pub fn builder() -> ScheduleBuilder<'a> {
+        ScheduleBuilder::default()
+    }
+}
+
+/// A builder for a schedule.
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleBuilder<'a> {
+    cron: &'a str,
+    timezone: Option<&'a str>,
+    job: Job<'a>,
+}
+
+impl<'a> ScheduleBuilder<'a> {
+    /// Cron expression to send jobs on.
+    pub fn cron(mut self, value: &'a str) -> Self {
+        self.cron = value;
+        self
+    }
+
+    /// IANA time zone to evaluate the cron expression in.
+    pub fn timezone(mut self, value: &'a str) -> Self {
+        self.timezone = Some(value);
+        self
+    }
+
+    /// Template for the jobs to be sent.
+    pub fn job(mut self, value: Job<'a>) -> Self {
+        self.job = value;
+        self
+    }
+
+    /// Creates a schedule.
+    pub fn build(self) -> Schedule<'a> {
+        Schedule {
+            cron: self.cron,
+            timezone: self.timezone,
+            job: self.job,
+        }
+    }
+}
+
+/// Schedule info.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+#[non_exhaustive]
+pub struct ScheduleDetails {
+    /// Name of the queue jobs are sent to.
+    #[sqlx(rename = "name")]
+    pub queue_name: String,
+
+    /// Cron expression.
+    pub cron: String,
+
+    /// Time zone the cron expression is evaluated in.
+    pub timezone: Option<String>,
+
+    /// Payload of the jobs.
+    pub data: Option<serde_json::Value>,
+
+    /// Options of the jobs.
+    pub options: Option<serde_json::Value>,
+
+    /// When this schedule was created.
+    pub created_at: DateTime<Utc>,
+
+    /// When this schedule was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Parsed cron expression along with the time zone to evaluate it in.
+#[derive(Debug, Clone)]
+pub(crate) struct CronSchedule {
+    cron: Cron,
+    tz: Tz,
+}
+
+impl CronSchedule {
+    pub(crate) fn parse(cron: &str, timezone: Option<&str>) -> Result<Self, Error> {
+        let tz = match timezone {
+            None => Tz::UTC,
+            Some(name) => name.parse().map_err(|_| Error::InvalidSchedule {
+                msg: format!("unknown time zone: {}", name),
+            })?,
+        };
+        let cron = Cron::new(cron)
+            .parse()
+            .map_err(|e| Error::InvalidSchedule {
+                msg: format!("invalid cron expression '{}': {}", cron, e),
+            })?;
+        Ok(CronSchedule { cron, tz })
+    }
+
+    /// Whether the cron expression fires at any time in `(after, until]`.
+    pub(crate) fn fires_between(&self, after: DateTime<Utc>, until: DateTime<Utc>) -> bool {
+        // the expression only fires on whole seconds
+        let after = after.trunc_subsecs(0).with_timezone(&self.tz);
+        self.cron
+            .find_next_occurrence(&after, false)
+            .is_ok_and(|next| next.with_timezone(&Utc) <= until)
+    }
+}
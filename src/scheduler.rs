@@ -0,0 +1,42 @@
+use crate::utils::AbortOnDrop;
+use crate::Client;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Sends jobs on schedules in the background until dropped.
+///
+/// It is safe to have a scheduler in each of your processes: the instances coordinate
+/// via a PostgreSQL advisory lock and the `cron_on` timestamp, so that each time a cron
+/// expression fires exactly one job is sent. Ticks missed while no scheduler was running
+/// are collapsed into one job.
+///
+/// Use [`Client::scheduler`] to get an instance.
+#[derive(Debug)]
+pub struct Scheduler {
+    interval: Duration,
+    _task: AbortOnDrop,
+}
+
+impl Scheduler {
+    pub(crate) fn start(client: Client, interval: Duration) -> Self {
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = client.send_scheduled_jobs().await {
+                    log::error!("Failed to send scheduled jobs: {:?}", e);
+                }
+            }
+        });
+        Scheduler {
+            interval,
+            _task: AbortOnDrop(task.abort_handle()),
+        }
+    }
+
+    /// How often this scheduler checks the schedules.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
@@ -49,6 +49,8 @@ pub(super) fn create_queue_table(schema: &str) -> String {
             retention_minutes int,
             dead_letter text REFERENCES {schema}.queue (name),
             partition_name text,
+            paused boolean not null default false,
+            backoff jsonb,
             created_on timestamptz not null default now(),
             updated_on timestamptz not null default now(),
             PRIMARY KEY (name) 
@@ -95,13 +97,33 @@ pub(super) fn create_job_table(schema: &str) -> String {
             output jsonb,
             dead_letter text,
             policy text,
+            parents uuid[],
+            on_parent_failure text,
+            backoff jsonb,
             CONSTRAINT job_pkey PRIMARY KEY (name, id)  
         ) PARTITION BY LIST (name);
+        {}
+        {}
         ",
-        JobState::Created
+        JobState::Created,
+        create_job_parents_index(schema),
+        create_job_id_index(schema),
     )
 }
 
+/// Index to look up jobs depending on a job, also created on every queue's partition.
+fn create_job_parents_index(schema: &str) -> String {
+    format!("CREATE INDEX IF NOT EXISTS job_parents ON {schema}.job USING gin (parents) WHERE parents IS NOT NULL;")
+}
+
+/// Index to look parents of a job up by their IDs alone, also created on every queue's partition.
+///
+/// The primary key starts with the queue name, which is not known for parents, and so each
+/// lookup still probes this index on every partition.
+fn create_job_id_index(schema: &str) -> String {
+    format!("CREATE INDEX IF NOT EXISTS job_id ON {schema}.job (id);")
+}
+
 pub(super) fn create_archive_table(schema: &str) -> String {
     format!(
         "
@@ -111,6 +133,90 @@ pub(super) fn create_archive_table(schema: &str) -> String {
             PRIMARY KEY (name, id)
         );
         CREATE INDEX IF NOT EXISTS archive_i1 ON {schema}.archive (archived_on);
+        {}
+        ",
+        create_archive_id_index(schema),
+    )
+}
+
+/// Index to look up archived parents of a job, see [`create_job_id_index`].
+fn create_archive_id_index(schema: &str) -> String {
+    format!("CREATE INDEX IF NOT EXISTS archive_id ON {schema}.archive (id);")
+}
+
+pub(super) fn create_schedule_table(schema: &str) -> String {
+    format!(
+        "
+        CREATE TABLE IF NOT EXISTS {schema}.schedule (
+            name text REFERENCES {schema}.queue ON DELETE CASCADE,
+            cron text not null,
+            timezone text,
+            data jsonb,
+            options jsonb,
+            created_on timestamptz not null default now(),
+            updated_on timestamptz not null default now(),
+            PRIMARY KEY (name)
+        );
+        "
+    )
+}
+
+/// Brings tables of an application installed by an earlier version of this crate up to date.
+///
+/// Does nothing if the core tables are missing, i.e. the application has not been
+/// installed by this crate or by PgBoss. Runs on every start, so each statement is only
+/// executed if there is something to upgrade: `IF NOT EXISTS` alone would still take
+/// (and wait for) an exclusive lock on the table.
+pub(super) fn upgrade_tables(schema: &str) -> String {
+    format!(
+        "
+        DO $$
+        BEGIN
+            IF to_regclass('{schema}.queue') IS NOT NULL AND to_regclass('{schema}.job') IS NOT NULL THEN
+                {}
+            END IF;
+        END $$;
+        ",
+        [
+            format!(
+                "IF to_regclass('{schema}.schedule') IS NULL THEN {} END IF;",
+                create_schedule_table(schema)
+            ),
+            add_column(schema, "queue", "paused", "boolean not null default false"),
+            add_column(schema, "job", "parents", "uuid[]"),
+            add_column(schema, "job", "on_parent_failure", "text"),
+            add_column(schema, "archive", "parents", "uuid[]"),
+            add_column(schema, "archive", "on_parent_failure", "text"),
+            add_column(schema, "queue", "backoff", "jsonb"),
+            add_column(schema, "job", "backoff", "jsonb"),
+            add_column(schema, "archive", "backoff", "jsonb"),
+            format!(
+                "IF to_regclass('{schema}.job_parents') IS NULL THEN {} END IF;",
+                create_job_parents_index(schema)
+            ),
+            format!(
+                "IF to_regclass('{schema}.job_id') IS NULL THEN {} END IF;",
+                create_job_id_index(schema)
+            ),
+            format!(
+                "IF to_regclass('{schema}.archive') IS NOT NULL AND to_regclass('{schema}.archive_id') IS NULL THEN {} END IF;",
+                create_archive_id_index(schema)
+            ),
+        ]
+        .join("\n"),
+    )
+}
+
+/// Statement adding a column to a table unless the table is missing or already has the column.
+fn add_column(schema: &str, table: &str, column: &str, definition: &str) -> String {
+    format!(
+        "
+        IF to_regclass('{schema}.{table}') IS NOT NULL AND NOT EXISTS (
+            SELECT 1 FROM information_schema.columns
+            WHERE table_schema = '{schema}' AND table_name = '{table}' AND column_name = '{column}'
+        ) THEN
+            ALTER TABLE {schema}.{table} ADD COLUMN {column} {definition};
+        END IF;
         "
     )
 }
@@ -1,5 +1,32 @@
 use crate::job::JobState;
 
+/// Columns of the `job` table, which the `archive` table has been created `LIKE`.
+const JOB_COLUMNS: [&str; 23] = [
+    "id",
+    "name",
+    "priority",
+    "data",
+    "state",
+    "retry_limit",
+    "retry_count",
+    "retry_delay",
+    "retry_backoff",
+    "start_after",
+    "started_on",
+    "singleton_key",
+    "singleton_on",
+    "expire_in",
+    "created_on",
+    "completed_on",
+    "keep_until",
+    "output",
+    "dead_letter",
+    "policy",
+    "parents",
+    "on_parent_failure",
+    "backoff",
+];
+
 pub(crate) fn check_if_app_installed(schema: &str) -> String {
     format!(
         "
@@ -33,6 +60,8 @@ pub(crate) fn get_queue(schema: &str) -> String {
             expire_seconds,
             retention_minutes,
             dead_letter,
+            paused,
+            backoff,
             created_on as created_at,
             updated_on as updated_at
         FROM {schema}.queue
@@ -53,6 +82,8 @@ pub(crate) fn get_queues(schema: &str) -> String {
             expire_seconds,
             retention_minutes,
             dead_letter,
+            paused,
+            backoff,
             created_on as created_at,
             updated_on as updated_at
         FROM {schema}.queue;
@@ -60,12 +91,108 @@ pub(crate) fn get_queues(schema: &str) -> String {
     )
 }
 
+pub(crate) fn lock_queue(schema: &str) -> String {
+    format!(
+        "
+        SELECT
+            name,
+            policy,
+            retry_limit,
+            retry_delay,
+            retry_backoff,
+            expire_seconds,
+            retention_minutes,
+            dead_letter,
+            paused,
+            backoff,
+            created_on as created_at,
+            updated_on as updated_at
+        FROM {schema}.queue
+        WHERE name = $1
+        FOR UPDATE;
+        "
+    )
+}
+
+/// Options are expected in the same format the `create_queue` function expects them in.
+pub(crate) fn update_queue(schema: &str) -> String {
+    format!(
+        "
+        UPDATE {schema}.queue SET
+            policy = $2->>'policy',
+            retry_limit = ($2->>'retryLimit')::int,
+            retry_delay = ($2->>'retryDelay')::int,
+            retry_backoff = ($2->>'retryBackoff')::bool,
+            expire_seconds = ($2->>'expireInSeconds')::int,
+            retention_minutes = ($2->>'retentionMinutes')::int,
+            dead_letter = $2->>'deadLetter',
+            backoff = $2->'backoff',
+            updated_on = now()
+        WHERE name = $1
+        RETURNING
+            name,
+            policy,
+            retry_limit,
+            retry_delay,
+            retry_backoff,
+            expire_seconds,
+            retention_minutes,
+            dead_letter,
+            paused,
+            backoff,
+            created_on as created_at,
+            updated_on as updated_at;
+        "
+    )
+}
+
+pub(crate) fn set_queue_paused(schema: &str) -> String {
+    format!("UPDATE {schema}.queue SET paused = $2, updated_on = now() WHERE name = $1;")
+}
+
+pub(crate) fn get_queue_size(schema: &str) -> String {
+    format!("SELECT COUNT(*) FROM {schema}.job WHERE name = $1 AND state < $2::{schema}.job_state;")
+}
+
+/// Gets stats on the queue with the name `$1` or on all the queues, if `$1` is `NULL`.
+pub(crate) fn get_queue_stats(schema: &str) -> String {
+    format!(
+        r#"
+        SELECT
+            q.name as queue_name,
+            COUNT(j.id) FILTER (WHERE j.state = '{0}' AND j.start_after < now()) as created,
+            COUNT(j.id) FILTER (WHERE j.state = '{0}' AND j.start_after >= now()) as deferred,
+            COUNT(j.id) FILTER (WHERE j.state = '{1}') as retry,
+            COUNT(j.id) FILTER (WHERE j.state = '{2}') as active,
+            COUNT(j.id) FILTER (WHERE j.state = '{3}') as completed,
+            COUNT(j.id) FILTER (WHERE j.state = '{4}') as cancelled,
+            COUNT(j.id) FILTER (WHERE j.state = '{5}') as failed
+        FROM {schema}.queue q LEFT JOIN {schema}.job j ON j.name = q.name
+        WHERE $1::text IS NULL OR q.name = $1
+        GROUP BY q.name
+        ORDER BY q.name;
+        "#,
+        JobState::Created,   // 0
+        JobState::Retry,     // 1
+        JobState::Active,    // 2
+        JobState::Completed, // 3
+        JobState::Cancelled, // 4
+        JobState::Failed,    // 5
+    )
+}
+
 pub(crate) fn fetch_jobs(schema: &str) -> String {
     format!(
         r#"
         WITH next AS (
-            SELECT id FROM {schema}.job
+            SELECT id FROM {schema}.job c
             WHERE name = $1 AND state < 'active' AND start_after < now()
+            AND NOT EXISTS (SELECT 1 FROM {schema}.queue WHERE name = $1 AND paused)
+            AND (c.parents IS NULL OR NOT EXISTS (
+                SELECT 1 FROM unnest(c.parents) pid
+                WHERE NOT EXISTS (SELECT 1 FROM {schema}.job p WHERE p.id = pid AND p.state = 'completed')
+                AND NOT EXISTS (SELECT 1 FROM {schema}.archive a WHERE a.id = pid AND a.state = 'completed')
+            ))
             ORDER BY priority DESC, created_on, id
             LIMIT $2
             FOR UPDATE
@@ -97,24 +224,34 @@ pub(crate) fn fetch_jobs(schema: &str) -> String {
             singleton_key,
             dead_letter,
             keep_until,
-            output;
+            output,
+            parents,
+            backoff;
         "#
     )
 }
 
-pub(crate) fn cancel_jobs(schema: &str) -> String {
+pub(crate) fn cancel_jobs(schema: &str, notify: bool) -> String {
     format!(
         r#"
-        WITH results AS (
+        WITH RECURSIVE results AS (
             UPDATE {schema}.job
             SET completed_on = now(), state = '{0}'::{schema}.job_state
             WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state < '{1}'::{schema}.job_state      
-            RETURNING 1
-        )
-        SELECT COUNT(*) from results;
+            RETURNING name, id
+        ),
+        {2}
+        {3}
         "#,
         JobState::Cancelled,
         JobState::Completed,
+        orphan_children(schema, "SELECT id FROM results", "parent job cancelled"),
+        count_results(
+            schema,
+            notify.then_some(
+                "SELECT name, id FROM results UNION ALL SELECT name, id FROM orphaned_results"
+            )
+        ),
     )
 }
 
@@ -134,6 +271,80 @@ pub(crate) fn resume_jobs(schema: &str) -> String {
     )
 }
 
+/// Moves `failed` (and, if `$3`, `completed`) jobs back to the queue,
+/// resetting their `retry_count` if `$4`.
+pub(crate) fn retry_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        WITH results AS (
+            UPDATE {schema}.job
+            SET
+                state = CASE WHEN $4 OR retry_count = 0 THEN '{0}'::{schema}.job_state ELSE '{1}'::{schema}.job_state END,
+                retry_count = CASE WHEN $4 THEN 0 ELSE retry_count END,
+                backoff = CASE WHEN $4 THEN backoff - 'last_delay' ELSE backoff END,
+                started_on = NULL,
+                completed_on = NULL,
+                start_after = now()
+            WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[]))
+                AND (state = '{2}'::{schema}.job_state OR ($3 AND state = '{3}'::{schema}.job_state))
+            RETURNING 1
+        )
+        SELECT COUNT(*) from results;
+        "#,
+        JobState::Created,   // 0
+        JobState::Retry,     // 1
+        JobState::Failed,    // 2
+        JobState::Completed, // 3
+    )
+}
+
+pub(crate) fn release_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        UPDATE {schema}.job
+        SET
+            state = CASE WHEN retry_count > 0 THEN '{0}'::{schema}.job_state ELSE '{1}'::{schema}.job_state END,
+            started_on = NULL,
+            start_after = now()
+        WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state = '{2}'::{schema}.job_state
+        RETURNING id;
+        "#,
+        JobState::Retry,   // 0
+        JobState::Created, // 1
+        JobState::Active,  // 2
+    )
+}
+
+pub(crate) fn touch_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        WITH results AS (
+            UPDATE {schema}.job
+            SET started_on = now()
+            WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state = '{0}'::{schema}.job_state
+            RETURNING 1
+        )
+        SELECT COUNT(*) from results;
+        "#,
+        JobState::Active,
+    )
+}
+
+pub(crate) fn report_progress(schema: &str) -> String {
+    format!(
+        r#"
+        WITH results AS (
+            UPDATE {schema}.job
+            SET output = $3
+            WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state = '{0}'::{schema}.job_state
+            RETURNING 1
+        )
+        SELECT COUNT(*) from results;
+        "#,
+        JobState::Active,
+    )
+}
+
 pub(crate) fn delete_jobs(schema: &str) -> String {
     format!(
         r#"
@@ -147,12 +358,80 @@ pub(crate) fn delete_jobs(schema: &str) -> String {
     )
 }
 
-pub(crate) fn fail_jobs(schema: &str) -> String {
+pub(crate) fn purge_queue(schema: &str) -> String {
     format!(
         r#"
-        WITH deleted_jobs AS (
+        WITH results AS (
             DELETE FROM {schema}.job
-            WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state < '{2}'::{schema}.job_state
+            WHERE name = $1 AND state = ANY($2::{schema}.job_state[])
+            RETURNING 1
+        )
+        SELECT COUNT(*) from results;
+        "#
+    )
+}
+
+pub(crate) fn fail_jobs(schema: &str, notify: bool) -> String {
+    fail_jobs_where(
+        schema,
+        notify,
+        true,
+        &format!(
+            "name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state < '{0}'::{schema}.job_state",
+            JobState::Completed,
+        ),
+        "$3::jsonb",
+    )
+}
+
+pub(crate) fn fail_jobs_permanently(schema: &str, notify: bool) -> String {
+    fail_jobs_where(
+        schema,
+        notify,
+        false,
+        &format!(
+            "name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state < '{0}'::{schema}.job_state",
+            JobState::Completed,
+        ),
+        "$3::jsonb",
+    )
+}
+
+pub(crate) fn expire_jobs(schema: &str, notify: bool) -> String {
+    fail_jobs_where(
+        schema,
+        notify,
+        true,
+        &format!(
+            "state = '{0}'::{schema}.job_state AND (started_on + expire_in) < now()",
+            JobState::Active,
+        ),
+        r#"'{"message": "job failed by timeout in active state"}'::jsonb"#,
+    )
+}
+
+/// Fails jobs matching the `condition` writing `output` (an SQL expression) to them.
+///
+/// Jobs that have not exhausted their retry attempts are transitioned to `retry`
+/// (unless not `retryable`), the rest are transitioned to `failed` and - if a dead
+/// letter queue has been specified - copied to the dead letter queue.
+fn fail_jobs_where(
+    schema: &str,
+    notify: bool,
+    retryable: bool,
+    condition: &str,
+    output: &str,
+) -> String {
+    let retry = match retryable {
+        true => "retry_count < retry_limit",
+        false => "false",
+    };
+    let delay = retry_delay();
+    format!(
+        r#"
+        WITH RECURSIVE deleted_jobs AS (
+            DELETE FROM {schema}.job
+            WHERE {condition}
             RETURNING *
         ),
         retried_jobs AS (
@@ -176,7 +455,10 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 keep_until,
                 dead_letter,
                 policy,
-                output
+                output,
+                parents,
+                on_parent_failure,
+                backoff
             )
             SELECT
                 id,
@@ -184,7 +466,7 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 priority,
                 data,
                 CASE
-                    WHEN retry_count < retry_limit THEN '{0}'::{schema}.job_state
+                    WHEN {retry} THEN '{0}'::{schema}.job_state
                     ELSE '{1}'::{schema}.job_state
                 END as state,
                 retry_limit,
@@ -192,12 +474,8 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 retry_delay,
                 retry_backoff,
                 CASE
-                    WHEN retry_count = retry_limit THEN start_after
-                    WHEN NOT retry_backoff THEN now() + retry_delay * interval '1'
-                    ELSE now() + (
-                        retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 +
-                        retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 * random()
-                    ) * interval '1'
+                    WHEN NOT ({retry}) THEN start_after
+                    ELSE now() + b.delay * interval '1'
                 END as start_after,
                 started_on,
                 singleton_key,
@@ -205,14 +483,21 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 expire_in,
                 created_on,
                 CASE
-                    WHEN retry_count < retry_limit THEN NULL
+                    WHEN {retry} THEN NULL
                     ELSE now()
                 END as completed_on,
                 keep_until,
                 dead_letter,
                 policy,        
-                $3::jsonb
-            FROM deleted_jobs
+                {output},
+                parents,
+                on_parent_failure,
+                CASE
+                    WHEN backoff->>'strategy' = 'decorrelated_jitter' AND {retry}
+                    THEN jsonb_set(backoff, '{{last_delay}}', to_jsonb(b.delay))
+                    ELSE backoff
+                END as backoff
+            FROM deleted_jobs, LATERAL ({delay}) b
             ON CONFLICT DO NOTHING
             RETURNING *
         ),
@@ -237,7 +522,10 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 keep_until,
                 dead_letter,
                 policy,
-                output
+                output,
+                parents,
+                on_parent_failure,
+                backoff
             )
             SELECT
                 id,
@@ -259,7 +547,10 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
                 keep_until,
                 dead_letter,
                 policy,
-                $3::jsonb
+                {output},
+                parents,
+                on_parent_failure,
+                backoff
             FROM deleted_jobs
             WHERE id NOT IN (SELECT id from retried_jobs)
             RETURNING *
@@ -273,28 +564,164 @@ pub(crate) fn fail_jobs(schema: &str) -> String {
             INSERT INTO {schema}.job (name, data, output, retry_limit, keep_until)
             SELECT dead_letter, data, output, retry_limit, keep_until + (keep_until - start_after)
             FROM results WHERE state = '{1}'::{schema}.job_state AND dead_letter IS NOT NULL AND NOT name = dead_letter
+        ),
+        {2}
+        {3}
+        "#,
+        JobState::Retry,  // 0
+        JobState::Failed, // 1
+        orphan_children(
+            schema,
+            &format!(
+                "SELECT id FROM results WHERE state = '{}'::{schema}.job_state",
+                JobState::Failed
+            ),
+            "parent job failed"
+        ),
+        count_results(
+            schema,
+            notify.then_some(&format!(
+                "SELECT name, id FROM results WHERE state = '{0}'::{schema}.job_state
+                UNION ALL
+                SELECT name, id FROM orphaned_results",
+                JobState::Failed,
+            ))
+        ),
+    )
+}
+
+/// Common table expressions cancelling (or failing, as per `on_parent_failure`) the jobs
+/// still waiting for any of the jobs selected by `parents`, and the jobs waiting for those,
+/// and so on, writing `message` to their output.
+///
+/// The affected jobs are returned by `orphaned_results`. To be used with `WITH RECURSIVE`.
+fn orphan_children(schema: &str, parents: &str, message: &str) -> String {
+    format!(
+        r#"
+        orphaned_jobs AS (
+            SELECT j.id FROM {schema}.job j
+            WHERE j.parents && ARRAY({parents}) AND j.state < '{0}'::{schema}.job_state
+            UNION
+            SELECT j.id FROM {schema}.job j JOIN orphaned_jobs o ON j.parents @> ARRAY[o.id]
+            WHERE j.state < '{0}'::{schema}.job_state
+        ),
+        orphaned_results AS (
+            UPDATE {schema}.job j
+            SET
+                state = CASE WHEN on_parent_failure = 'fail' THEN '{1}'::{schema}.job_state ELSE '{2}'::{schema}.job_state END,
+                completed_on = now(),
+                output = jsonb_build_object('message', '{message}')
+            FROM orphaned_jobs o
+            WHERE j.id = o.id
+            RETURNING j.name, j.id
         )
-        SELECT COUNT(*) FROM results
         "#,
-        JobState::Retry,     // 0
+        JobState::Active,    // 0
         JobState::Failed,    // 1
-        JobState::Completed, // 2
+        JobState::Cancelled, // 2
+    )
+}
+
+/// Query selecting the `delay` (in seconds) before the next retry attempt of a job
+/// according to its backoff strategy (see `Backoff`).
+///
+/// Jobs stored before the strategy could be specified fall back to `retry_backoff`.
+fn retry_delay() -> &'static str {
+    "
+    SELECT CASE COALESCE(backoff->>'strategy', CASE WHEN retry_backoff THEN 'exponential' ELSE 'fixed' END)
+        WHEN 'linear' THEN retry_delay * (retry_count + 1)
+        WHEN 'exponential' THEN
+            retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 +
+            retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 * random()
+        WHEN 'capped_exponential' THEN LEAST(
+            (backoff->>'max_delay')::float8,
+            retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 +
+            retry_delay * 2 ^ LEAST(16, retry_count + 1) / 2 * random()
+        )
+        WHEN 'decorrelated_jitter' THEN LEAST(
+            (backoff->>'max_delay')::float8,
+            retry_delay + random() * (3 * GREATEST(retry_delay, COALESCE((backoff->>'last_delay')::float8, retry_delay)) - retry_delay)
+        )
+        ELSE retry_delay
+    END::float8 as delay
+    "
+}
+
+/// Final part of a statement counting rows of its `results` query.
+///
+/// If `done` (a query selecting `name` and `id` of jobs in terminal states) is given,
+/// also notifies those waiting for these jobs (see `Client::wait_for_job`). The notifications
+/// are only sent if referenced, hence the additional column.
+fn count_results(schema: &str, done: Option<&str>) -> String {
+    match done {
+        None => "SELECT COUNT(*) FROM results;".to_string(),
+        Some(done) => format!(
+            "
+            , notified AS (
+                SELECT pg_notify({}, id::text) FROM ({done}) d
+            )
+            SELECT (SELECT COUNT(*) FROM results), (SELECT COUNT(*) FROM notified);
+            ",
+            super::done_channel(schema),
+        ),
+    }
+}
+
+pub(crate) fn get_done_channel(schema: &str) -> String {
+    format!("SELECT {};", super::done_channel(schema))
+}
+
+/// Moves jobs in terminal states past their `keep_until` to the archive.
+///
+/// Note that `keep_until` already accounts for the queue's `retention_minutes`
+/// (see `create_job` procedure).
+pub(crate) fn archive_jobs(schema: &str) -> String {
+    let columns = JOB_COLUMNS.join(", ");
+    format!(
+        r#"
+        WITH archived_jobs AS (
+            DELETE FROM {schema}.job
+            WHERE state > '{0}'::{schema}.job_state AND keep_until < now()
+            RETURNING {columns}
+        ),
+        results AS (
+            INSERT INTO {schema}.archive ({columns})
+            SELECT {columns} FROM archived_jobs
+            RETURNING 1
+        )
+        SELECT COUNT(*) FROM results;
+        "#,
+        JobState::Active,
+    )
+}
+
+pub(crate) fn delete_archived_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        WITH results AS (
+            DELETE FROM {schema}.archive
+            WHERE archived_on < now() - $1 * interval '1 second'
+            RETURNING 1
+        )
+        SELECT COUNT(*) FROM results;
+        "#
     )
 }
 
-pub(crate) fn complete_jobs(schema: &str) -> String {
+pub(crate) fn complete_jobs(schema: &str, notify: bool) -> String {
     format!(
         r#"
         WITH results AS (
             UPDATE {schema}.job
             SET state = '{1}'::{schema}.job_state, completed_on = now(), output = $3::jsonb
             WHERE name = $1 AND id IN (SELECT UNNEST($2::uuid[])) AND state = '{0}'::{schema}.job_state
-            RETURNING 1
+            RETURNING name, id
         )
-        SELECT COUNT(*) from results;
+        {2}
         "#,
         JobState::Active,    // 0
         JobState::Completed, // 1
+        count_results(schema, notify.then_some("SELECT name, id FROM results")),
     )
 }
 //                   id                  |  name       | priority | data |   state   | retry_limit | retry_count | retry_delay | retry_backoff |          start_after          |          started_on           | singleton_key | singleton_on | expire_in |          created_on           |         completed_on          |          keep_until           |         output                  | dead_letter |  policy
@@ -324,9 +751,238 @@ pub(crate) fn get_job_info(schema: &str) -> String {
             singleton_key,
             keep_until,
             dead_letter,
-            output
+            output,
+            parents,
+            backoff
         FROM {schema}.job
         WHERE name = $1 and id = $2;
         "#,
     )
 }
+
+/// Looks jobs up by their IDs, optionally in the given queue only (`$1`)
+/// and optionally falling back to the archive (`$3`), preserving the order of IDs.
+pub(crate) fn get_jobs_info(schema: &str) -> String {
+    let columns = r#"
+            id,
+            name,
+            data,
+            EXTRACT(epoch FROM expire_in)::float8 as expire_in,
+            state,
+            policy,
+            priority,
+            retry_limit,
+            retry_delay,
+            retry_count,
+            retry_backoff,
+            start_after,
+            created_on as created_at,
+            started_on as started_at,
+            singleton_on as singleton_at,
+            completed_on as completed_at,
+            singleton_key,
+            keep_until,
+            dead_letter,
+            output,
+            parents,
+            backoff
+    "#;
+    format!(
+        r#"
+        SELECT {columns} FROM (
+            SELECT {job_columns} FROM {schema}.job
+            WHERE ($1::text IS NULL OR name = $1) AND id = ANY($2)
+            UNION ALL
+            SELECT {job_columns} FROM {schema}.archive
+            WHERE $3 AND ($1::text IS NULL OR name = $1) AND id = ANY($2)
+        ) j
+        ORDER BY array_position($2, id);
+        "#,
+        job_columns = JOB_COLUMNS.join(", "),
+    )
+}
+
+/// Lists jobs matching the optional filters (`$1` to `$7`), keyset-paginated by `(created_on, id)`.
+pub(crate) fn query_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        SELECT
+            id,
+            name,
+            data,
+            EXTRACT(epoch FROM expire_in)::float8 as expire_in,
+            state,
+            policy,
+            priority,
+            retry_limit,
+            retry_delay,
+            retry_count,
+            retry_backoff,
+            start_after,
+            created_on as created_at,
+            started_on as started_at,
+            singleton_on as singleton_at,
+            completed_on as completed_at,
+            singleton_key,
+            keep_until,
+            dead_letter,
+            output,
+            parents,
+            backoff
+        FROM {schema}.job
+        WHERE ($1::text IS NULL OR name = $1)
+            AND ($2::{schema}.job_state[] IS NULL OR state = ANY($2::{schema}.job_state[]))
+            AND ($3::timestamptz IS NULL OR created_on >= $3)
+            AND ($4::timestamptz IS NULL OR created_on < $4)
+            AND ($5::text IS NULL OR singleton_key = $5)
+            AND ($6::text IS NULL OR dead_letter = $6)
+            AND ($7::jsonb IS NULL OR data @> $7)
+            AND ($8::timestamptz IS NULL OR (created_on, id) > ($8, $9::uuid))
+        ORDER BY created_on, id
+        LIMIT $10;
+        "#
+    )
+}
+
+pub(crate) fn get_child_jobs(schema: &str) -> String {
+    format!(
+        r#"
+        SELECT
+            id,
+            name,
+            data,
+            EXTRACT(epoch FROM expire_in)::float8 as expire_in,
+            state,
+            policy,
+            priority,
+            retry_limit,
+            retry_delay,
+            retry_count,
+            retry_backoff,
+            start_after,
+            created_on as created_at,
+            started_on as started_at,
+            singleton_on as singleton_at,
+            completed_on as completed_at,
+            singleton_key,
+            keep_until,
+            dead_letter,
+            output,
+            parents,
+            backoff
+        FROM {schema}.job
+        WHERE parents @> ARRAY[$1::uuid]
+        ORDER BY created_on, id;
+        "#
+    )
+}
+
+pub(crate) fn try_lock_maintenance(schema: &str) -> String {
+    format!(
+        "SELECT pg_try_advisory_xact_lock({});",
+        super::advisory_lock_key(schema, ".maintenance")
+    )
+}
+
+pub(crate) fn check_if_maintenance_due(schema: &str) -> String {
+    format!(
+        "
+        SELECT NOT EXISTS (
+            SELECT 1 FROM {schema}.version WHERE maintained_on > now() - $1 * interval '1 second'
+        );
+        "
+    )
+}
+
+pub(crate) fn set_maintained_on(schema: &str) -> String {
+    format!("UPDATE {schema}.version SET maintained_on = now();")
+}
+
+pub(crate) fn get_schedules(schema: &str) -> String {
+    format!(
+        "
+        SELECT
+            name,
+            cron,
+            timezone,
+            data,
+            options,
+            created_on as created_at,
+            updated_on as updated_at
+        FROM {schema}.schedule
+        ORDER BY name;
+        "
+    )
+}
+
+pub(crate) fn upsert_schedule(schema: &str) -> String {
+    format!(
+        "
+        INSERT INTO {schema}.schedule (name, cron, timezone, data, options)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (name) DO UPDATE SET
+            cron = EXCLUDED.cron,
+            timezone = EXCLUDED.timezone,
+            data = EXCLUDED.data,
+            options = EXCLUDED.options,
+            updated_on = now();
+        "
+    )
+}
+
+pub(crate) fn delete_schedule(schema: &str) -> String {
+    format!("DELETE FROM {schema}.schedule WHERE name = $1;")
+}
+
+pub(crate) fn try_lock_cron(schema: &str) -> String {
+    format!(
+        "SELECT pg_try_advisory_xact_lock({});",
+        super::advisory_lock_key(schema, ".cron")
+    )
+}
+
+pub(crate) fn get_cron_on(schema: &str) -> String {
+    format!("SELECT cron_on, now() FROM {schema}.version;")
+}
+
+pub(crate) fn set_cron_on(schema: &str) -> String {
+    // never moves backwards, see `Client::send_scheduled_jobs`
+    format!("UPDATE {schema}.version SET cron_on = GREATEST(cron_on, now());")
+}
+
+pub(crate) fn subscribe(schema: &str) -> String {
+    format!(
+        "
+        INSERT INTO {schema}.subscription (event, name)
+        VALUES ($1, $2)
+        ON CONFLICT (event, name) DO UPDATE SET updated_on = now();
+        "
+    )
+}
+
+pub(crate) fn unsubscribe(schema: &str) -> String {
+    format!("DELETE FROM {schema}.subscription WHERE event = $1 AND name = $2;")
+}
+
+pub(crate) fn get_subscribed_queues(schema: &str) -> String {
+    format!("SELECT name FROM {schema}.subscription WHERE event = $1 ORDER BY name;")
+}
+
+/// Creates a job in each of the queues subscribed to the event.
+pub(crate) fn publish(schema: &str) -> String {
+    format!(
+        "
+        SELECT {schema}.create_job(NULL, name, $2, $3) as id
+        FROM {schema}.subscription
+        WHERE event = $1
+        ORDER BY name;
+        "
+    )
+}
+
+pub(crate) fn get_notify_channels(schema: &str) -> String {
+    format!(
+        "SELECT {} FROM UNNEST($1::text[]) as queue_name;",
+        super::notify_channel(schema, "queue_name")
+    )
+}
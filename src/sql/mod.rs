@@ -11,14 +11,40 @@ where
         BEGIN;
         SET LOCAL lock_timeout = '30s';
         SET LOCAL idle_in_transaction_session_timeout = '30s';
-        SELECT pg_advisory_xact_lock(('x' || encode(sha224((current_database() || '.pgboss.{schema}')::bytea), 'hex'))::bit(64)::bigint);
+        SELECT pg_advisory_xact_lock({});
         {};
         COMMIT;
         ",
+        advisory_lock_key(schema, ""),
         stmts.into_iter().collect::<Vec<_>>().join("\n"),
     )
 }
 
+/// Expression evaluating to the key of a schema-scoped advisory lock.
+///
+/// Different `scope`s result in different locks for the same schema.
+fn advisory_lock_key(schema: &str, scope: &str) -> String {
+    format!("('x' || encode(sha224((current_database() || '.pgboss.{schema}{scope}')::bytea), 'hex'))::bit(64)::bigint")
+}
+
+/// Expression evaluating to the name of the channel to notify consumers
+/// of the queue with the name `queue_name` (an SQL expression) on.
+///
+/// Hashing the names, since a channel name cannot be longer than 63 bytes.
+fn notify_channel(schema: &str, queue_name: &str) -> String {
+    format!("'pgboss_' || md5('{schema}.' || {queue_name})")
+}
+
+/// Expression evaluating to the name of the channel to notify those waiting for jobs
+/// to be done on, with the job's ID as payload.
+///
+/// One channel for all the queues, so that a client can wait for any number of jobs
+/// on a single connection. Schema names cannot contain colons, so this never clashes
+/// with a [`notify_channel`].
+fn done_channel(schema: &str) -> String {
+    format!("'pgboss_' || md5('{schema}:done')")
+}
+
 ///
 /// \d
 ///```md
@@ -28,9 +54,10 @@ where
 /// pgboss | archive      | table             | pgboss_user
 /// pgboss | job          | partitioned table | pgboss_user
 /// pgboss | queue        | table             | pgboss_user
+/// pgboss | schedule     | table             | pgboss_user
 /// pgboss | subscription | table             | pgboss_user
 /// pgboss | version      | table             | pgboss_user
-/// (5 rows)
+/// (6 rows)
 /// ```
 ///
 pub(crate) fn install_app(schema: &str) -> String {
@@ -44,6 +71,7 @@ pub(crate) fn install_app(schema: &str) -> String {
             ddl::create_subscription_table(schema),
             ddl::create_job_table(schema),
             ddl::create_archive_table(schema),
+            ddl::create_schedule_table(schema),
             proc::create_create_queue_function(schema),
             proc::create_delete_queue_function(schema),
             proc::create_create_job_function(schema),
@@ -57,6 +85,7 @@ pub(crate) fn install_functions(schema: &str) -> String {
     locked(
         schema,
         [
+            ddl::upgrade_tables(schema),
             proc::create_create_queue_function(schema),
             proc::create_delete_queue_function(schema),
             proc::create_create_job_function(schema),
@@ -20,6 +20,7 @@ pub(super) fn create_create_queue_function(schema: &str) -> String {
                 expire_seconds,
                 retention_minutes,
                 dead_letter,
+                backoff,
                 partition_name
             )
             VALUES (
@@ -31,6 +32,7 @@ pub(super) fn create_create_queue_function(schema: &str) -> String {
                 (options->>'expireInSeconds')::int,
                 (options->>'retentionMinutes')::int,
                 options->>'deadLetter',
+                options->'backoff',
                 table_name
             ) ON CONFLICT DO NOTHING RETURNING created_on INTO queue_created_on;
             
@@ -104,6 +106,8 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
         CREATE OR REPLACE FUNCTION {schema}.create_job(job_id uuid, name text, data jsonb, options jsonb, OUT inserted_id uuid)
         RETURNS uuid AS
         $$
+        DECLARE
+            inserted_start_after timestamptz;
         BEGIN
         INSERT INTO {schema}.job (
             id,
@@ -119,7 +123,12 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
             retry_limit,
             retry_delay,
             retry_backoff,
-            policy
+            policy,
+            state,
+            completed_on,
+            parents,
+            on_parent_failure,
+            backoff
         )
         SELECT
             job_id_provided_or_generated,
@@ -141,12 +150,21 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
             END as keep_until,
             COALESCE(j.retry_limit, q.retry_limit, 2) as retry_limit,
             CASE
-                WHEN COALESCE(j.retry_backoff, q.retry_backoff, false)
+                WHEN b.backoff->>'strategy' <> 'fixed'
                 THEN GREATEST(COALESCE(j.retry_delay, q.retry_delay), 1)
                 ELSE COALESCE(j.retry_delay, q.retry_delay, 0)
             END as retry_delay,
             COALESCE(j.retry_backoff, q.retry_backoff, false) as retry_backoff,
-            q.policy
+            q.policy,
+            CASE
+                WHEN NOT parent_failed THEN '{0}'::{schema}.job_state
+                WHEN on_parent_failure = 'fail' THEN '{1}'::{schema}.job_state
+                ELSE '{2}'::{schema}.job_state
+            END as state,
+            CASE WHEN parent_failed THEN now() END as completed_on,
+            parents,
+            on_parent_failure,
+            b.backoff
         FROM (
             SELECT 
                 COALESCE(job_id, gen_random_uuid()) as job_id_provided_or_generated,
@@ -155,6 +173,8 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
                 COALESCE((options->>'priority')::int, 0) as priority,
                 CASE
                     WHEN right(options->>'start_after', 1) = 'Z' THEN CAST(options->>'start_after' as timestamptz)
+                    WHEN options->>'start_after' IS NULL AND (options->>'singleton_offset')::integer > 0 AND (options->>'singleton_for')::integer > 0
+                    THEN to_timestamp((options->>'singleton_for')::integer * floor((date_part('epoch', now()) + (options->>'singleton_offset')::integer) / (options->>'singleton_for')::integer))
                     ELSE now() + CAST(COALESCE(options->>'start_after','0') as interval)
                 END as start_after,
                 options->>'singleton_key' as singleton_key,
@@ -167,13 +187,45 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
                 options->>'keep_until' as keep_until,
                 (options->>'retry_limit')::integer as retry_limit,
                 (options->>'retry_delay')::integer as retry_delay,
-                (options->>'retry_backoff')::boolean as retry_backoff
-            ) j JOIN {schema}.queue q ON j.name = q.name
-        RETURNING id INTO inserted_id;
+                (options->>'retry_backoff')::boolean as retry_backoff,
+                options->'backoff' as backoff,
+                d.parents,
+                CASE WHEN d.parents IS NOT NULL THEN COALESCE(options->>'on_parent_failure', 'cancel') END as on_parent_failure,
+                -- the job would otherwise wait for a parent which is never going to complete
+                d.parents IS NOT NULL AND EXISTS (
+                    SELECT 1 FROM {schema}.job p
+                    WHERE p.id = ANY(d.parents) AND p.state IN ('{1}'::{schema}.job_state, '{2}'::{schema}.job_state)
+                    UNION ALL
+                    SELECT 1 FROM {schema}.archive a
+                    WHERE a.id = ANY(d.parents) AND a.state IN ('{1}'::{schema}.job_state, '{2}'::{schema}.job_state)
+                ) as parent_failed
+            FROM (
+                SELECT (SELECT array_agg(p::uuid) FROM jsonb_array_elements_text(options->'parents') p) as parents
+            ) d
+            ) j JOIN {schema}.queue q ON j.name = q.name,
+            -- settings of the job take precedence over those of the queue
+            LATERAL (
+                SELECT COALESCE(
+                    j.backoff,
+                    CASE WHEN j.retry_backoff THEN '{{"strategy": "exponential"}}'::jsonb WHEN NOT j.retry_backoff THEN '{{"strategy": "fixed"}}'::jsonb END,
+                    q.backoff,
+                    CASE WHEN q.retry_backoff THEN '{{"strategy": "exponential"}}'::jsonb ELSE '{{"strategy": "fixed"}}'::jsonb END
+                ) as backoff
+            ) b
+        RETURNING id, start_after INTO inserted_id, inserted_start_after;
+
+        -- deferred jobs will be picked up by consumers when polling
+        IF inserted_id IS NOT NULL AND COALESCE((options->>'notify')::boolean, false) AND inserted_start_after <= now() THEN
+            PERFORM pg_notify({notify_channel}, inserted_id::text);
+        END IF;
         END;
         $$
         LANGUAGE plpgsql;
-        "#
+        "#,
+        JobState::Created,   // 0
+        JobState::Failed,    // 1
+        JobState::Cancelled, // 2
+        notify_channel = super::notify_channel(schema, "name"),
     )
 }
 
@@ -184,3 +236,15 @@ pub(crate) fn create_create_job_function(schema: &str) -> String {
 pub(crate) fn create_job(schema: &str) -> String {
     format!("SELECT {schema}.create_job($1, $2, $3, $4);")
 }
+
+/// Creates jobs from arrays of IDs (`$1`), queue names (`$2`), payloads (`$3`), and options (`$4`)
+/// returning the IDs of the created jobs in the same order.
+pub(crate) fn create_jobs(schema: &str) -> String {
+    format!(
+        "
+        SELECT {schema}.create_job(j.id, j.name, j.data, j.options)
+        FROM UNNEST($1::uuid[], $2::text[], $3::jsonb[], $4::jsonb[]) WITH ORDINALITY as j(id, name, data, options, position)
+        ORDER BY j.position;
+        "
+    )
+}
@@ -0,0 +1,52 @@
+use super::Supervisor;
+use crate::Client;
+use std::time::Duration;
+
+/// Builder for [`Supervisor`].
+///
+/// Use [`Client::supervisor`] to get an instance.
+#[derive(Debug)]
+pub struct SupervisorBuilder {
+    client: Client,
+    interval: Duration,
+    delete_after: Duration,
+}
+
+impl SupervisorBuilder {
+    pub(crate) fn new(client: Client) -> Self {
+        SupervisorBuilder {
+            client,
+            interval: Duration::from_secs(60 * 2),
+            delete_after: Duration::from_secs(60 * 60 * 24 * 7),
+        }
+    }
+
+    /// How often to attempt to maintain the system.
+    ///
+    /// The first attempt is made right after the supervisor has been started.
+    /// Defaults to `2` minutes.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if zero duration is given.
+    pub fn interval(mut self, value: Duration) -> Self {
+        assert!(!value.is_zero(), "interval should be greater than zero");
+        self.interval = value;
+        self
+    }
+
+    /// For how long to keep archived jobs before deleting them.
+    ///
+    /// Defaults to `7` days.
+    pub fn delete_after(mut self, value: Duration) -> Self {
+        self.delete_after = value;
+        self
+    }
+
+    /// Creates a supervisor and starts it in the background.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn start(self) -> Supervisor {
+        Supervisor::start(self.client, self.interval, self.delete_after)
+    }
+}
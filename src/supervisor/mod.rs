@@ -0,0 +1,111 @@
+use crate::utils::AbortOnDrop;
+use crate::{Client, MaintenanceOutcome, MaintenanceRun};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+mod builder;
+
+pub use builder::SupervisorBuilder;
+
+/// Maintains the system in the background until dropped.
+///
+/// Every [`SupervisorBuilder::interval`] the supervisor attempts to run
+/// [`Client::maintain`]. It is safe to have a supervisor in each of your processes:
+/// the instances coordinate via a PostgreSQL advisory lock and the `maintained_on`
+/// timestamp, so that maintenance is performed by one instance at a time and
+/// no more often than once per interval.
+///
+/// Use [`Client::supervisor`] to get an instance.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use std::time::Duration;
+/// use pgboss::Client;
+///
+/// let c = Client::connect().await.unwrap();
+/// let supervisor = c
+///     .supervisor()
+///     .interval(Duration::from_secs(60))
+///     .delete_after(Duration::from_secs(60 * 60 * 24))
+///     .start();
+/// // ...
+/// if let Some(run) = supervisor.last_run() {
+///     println!("Last maintenance run: {:?}", run);
+/// }
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct Supervisor {
+    interval: Duration,
+    delete_after: Duration,
+    last_run: Arc<Mutex<Option<MaintenanceRun>>>,
+    _task: AbortOnDrop,
+}
+
+impl Supervisor {
+    pub(crate) fn start(client: Client, interval: Duration, delete_after: Duration) -> Self {
+        let last_run: Arc<Mutex<Option<MaintenanceRun>>> = Default::default();
+        let task = tokio::spawn(Self::supervise(
+            client,
+            interval,
+            delete_after,
+            last_run.clone(),
+        ));
+        Supervisor {
+            interval,
+            delete_after,
+            last_run,
+            _task: AbortOnDrop(task.abort_handle()),
+        }
+    }
+
+    async fn supervise(
+        client: Client,
+        interval: Duration,
+        delete_after: Duration,
+        last_run: Arc<Mutex<Option<MaintenanceRun>>>,
+    ) {
+        // ticks of different instances (as well as of the same instance) will drift,
+        // so considering maintenance due a bit earlier, otherwise the instance that
+        // performed maintenance last time could skip its turn and we would end up
+        // maintaining once per two intervals
+        let due_after = interval.mul_f64(0.9);
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let started_at = Utc::now();
+            let outcome = match client.maintain_if_due(due_after, delete_after).await {
+                Ok(Some(details)) => MaintenanceOutcome::Performed(details),
+                Ok(None) => MaintenanceOutcome::Skipped,
+                Err(e) => {
+                    log::error!("Failed to perform maintenance: {:?}", e);
+                    MaintenanceOutcome::Failed(Arc::new(e))
+                }
+            };
+            let run = MaintenanceRun {
+                started_at,
+                finished_at: Utc::now(),
+                outcome,
+            };
+            *last_run.lock().expect("not poisoned") = Some(run);
+        }
+    }
+
+    /// How often this supervisor attempts to maintain the system.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// For how long archived jobs are kept before being deleted.
+    pub fn delete_after(&self) -> Duration {
+        self.delete_after
+    }
+
+    /// Details on the latest completed run, if any.
+    pub fn last_run(&self) -> Option<MaintenanceRun> {
+        self.last_run.lock().expect("not poisoned").clone()
+    }
+}
@@ -2,6 +2,7 @@ use crate::Error;
 use serde::Serializer;
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::time::Duration;
+use tokio::task::AbortHandle;
 
 pub(crate) async fn create_pool(url: Option<&str>) -> Result<PgPool, Error> {
     let pool = match url {
@@ -50,3 +51,13 @@ where
         Some(dur) => serializer.serialize_u64(dur.as_secs() / 60),
     }
 }
+
+/// Aborts the task when dropped.
+#[derive(Debug)]
+pub(crate) struct AbortOnDrop(pub(crate) AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
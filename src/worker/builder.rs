@@ -0,0 +1,213 @@
+use super::{BoxedHandler, ShutdownSignal, Worker};
+use crate::{Client, JobDetails};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(doc)]
+use super::StopDetails;
+#[cfg(doc)]
+use crate::Error;
+
+/// Builder for [`Worker`].
+///
+/// Use [`Client::worker`] to get an instance.
+pub struct WorkerBuilder {
+    client: Client,
+    handlers: HashMap<String, BoxedHandler>,
+    concurrency: usize,
+    poll_interval: Duration,
+    listen: bool,
+    shutdown_signal: Option<ShutdownSignal>,
+    shutdown_timeout: Duration,
+}
+
+impl std::fmt::Debug for WorkerBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerBuilder")
+            .field("client", &self.client)
+            .field("queues", &self.handlers.keys().collect::<Vec<_>>())
+            .field("concurrency", &self.concurrency)
+            .field("poll_interval", &self.poll_interval)
+            .field("listen", &self.listen)
+            .field("shutdown_timeout", &self.shutdown_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WorkerBuilder {
+    pub(crate) fn new(client: Client) -> Self {
+        WorkerBuilder {
+            client,
+            handlers: HashMap::new(),
+            concurrency: 1,
+            poll_interval: Duration::from_secs(2),
+            listen: false,
+            shutdown_signal: None,
+            shutdown_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Register a handler for jobs from the queue with this name.
+    ///
+    /// When the handler resolves to `Ok`, the job is marked as `completed`
+    /// with the returned value stored as the job's `output`. When the handler
+    /// resolves to `Err` (or panics), the job is failed with the error message
+    /// stored in `output`, and will be retried as per its retry settings.
+    ///
+    /// Registering a handler for the same queue twice will replace the former one.
+    pub fn register<Q, H, F, O, E>(mut self, queue_name: Q, handler: H) -> Self
+    where
+        Q: Into<String>,
+        H: Fn(JobDetails) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<O, E>> + Send + 'static,
+        O: Into<serde_json::Value>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let handler: BoxedHandler = Arc::new(move |job| {
+            let fut = handler(job);
+            Box::pin(async move { fut.await.map(Into::into).map_err(Into::into) })
+        });
+        self.handlers.insert(queue_name.into(), handler);
+        self
+    }
+
+    /// Register a handler for jobs with payloads of type `T` from the queue with this name.
+    ///
+    /// Works just like [`WorkerBuilder::register`], but the job's payload is
+    /// deserialized before being handed over to the handler. If the payload cannot
+    /// be deserialized, the handler is not called and the job is failed with
+    /// [`Error::Payload`] message stored in `output`.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # use pgboss::{Client, JobDetails};
+    /// # let c = Client::connect().await.unwrap();
+    /// #[derive(serde::Deserialize)]
+    /// struct Resize {
+    ///     width: u32,
+    ///     height: u32,
+    /// }
+    ///
+    /// let worker = c
+    ///     .worker()
+    ///     .register_typed("resize", |job: JobDetails<Resize>| async move {
+    ///         println!("resizing to {}x{}", job.data.width, job.data.height);
+    ///         Ok::<_, std::io::Error>(())
+    ///     })
+    ///     .build();
+    /// # });
+    /// ```
+    pub fn register_typed<T, Q, H, F, O, E>(self, queue_name: Q, handler: H) -> Self
+    where
+        T: DeserializeOwned + Send + 'static,
+        Q: Into<String>,
+        H: Fn(JobDetails<T>) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<O, E>> + Send + 'static,
+        O: Into<serde_json::Value>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.register(queue_name, move |job: JobDetails| {
+            let fut = job.try_into_typed().map(&handler);
+            async move {
+                match fut {
+                    Ok(fut) => fut.await.map_err(Into::into),
+                    Err(e) => Err(e.into()),
+                }
+            }
+        })
+    }
+
+    /// Maximum number of jobs processed simultaneously.
+    ///
+    /// This limit is shared by all the registered queues. Defaults to `1`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `0` is given.
+    pub fn concurrency(mut self, value: usize) -> Self {
+        assert!(value > 0, "concurrency should be greater than zero");
+        self.concurrency = value;
+        self
+    }
+
+    /// For how long to wait before checking a drained queue again.
+    ///
+    /// Defaults to `2` seconds.
+    pub fn poll_interval(mut self, value: Duration) -> Self {
+        self.poll_interval = value;
+        self
+    }
+
+    /// Whether to wake up as soon as a job is sent to any of the registered queues.
+    ///
+    /// If set to `true`, the worker will use a [`Listener`](crate::Listener) and
+    /// will only fall back to polling every [`WorkerBuilder::poll_interval`] for jobs
+    /// that have been deferred, retried or sent without notifying consumers
+    /// (see [`ClientBuilder::notify`](crate::ClientBuilder::notify)). The listener keeps
+    /// a connection of its own open, see [`Client::listen`]. Defaults to `false`.
+    pub fn listen(mut self, value: bool) -> Self {
+        self.listen = value;
+        self
+    }
+
+    /// Future that, once resolved, makes the worker stop gracefully.
+    ///
+    /// When the signal fires, the worker stops fetching new jobs and waits
+    /// for the handlers that are still running for up to [`WorkerBuilder::shutdown_timeout`].
+    /// Jobs that did not finish in time are released back to the queue
+    /// (see [`Client::release_jobs`]) and reported via [`StopDetails`].
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// # use pgboss::{Client, JobDetails};
+    /// # let c = Client::connect().await.unwrap();
+    /// let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    /// let worker = c
+    ///     .worker()
+    ///     .register("qname", |_job: JobDetails| async { Ok::<_, std::io::Error>(()) })
+    ///     .with_graceful_shutdown(async {
+    ///         let _ = rx.await;
+    ///     })
+    ///     .build();
+    /// let handle = tokio::spawn(worker.run());
+    ///
+    /// // e.g. when the process receives a termination signal
+    /// tx.send(()).unwrap();
+    /// let details = handle.await.unwrap().unwrap();
+    /// println!("Released jobs: {:?}", details.released_jobs);
+    /// # });
+    /// ```
+    pub fn with_graceful_shutdown<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.shutdown_signal = Some(Box::pin(signal));
+        self
+    }
+
+    /// For how long to wait for running handlers to finish during graceful shutdown.
+    ///
+    /// Defaults to `30` seconds.
+    pub fn shutdown_timeout(mut self, value: Duration) -> Self {
+        self.shutdown_timeout = value;
+        self
+    }
+
+    /// Creates a worker.
+    pub fn build(self) -> Worker {
+        Worker {
+            client: self.client,
+            handlers: self.handlers,
+            concurrency: self.concurrency,
+            poll_interval: self.poll_interval,
+            listen: self.listen,
+            shutdown_signal: self
+                .shutdown_signal
+                .unwrap_or_else(|| Box::pin(std::future::pending())),
+            shutdown_timeout: self.shutdown_timeout,
+        }
+    }
+}
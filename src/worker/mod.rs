@@ -0,0 +1,246 @@
+use crate::utils::AbortOnDrop;
+use crate::{Client, Error, JobDetails};
+use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+mod builder;
+
+pub use builder::WorkerBuilder;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+type HandlerResult = Result<serde_json::Value, BoxError>;
+
+pub(crate) type BoxedHandler =
+    Arc<dyn Fn(JobDetails) -> Pin<Box<dyn Future<Output = HandlerResult> + Send>> + Send + Sync>;
+
+pub(crate) type ShutdownSignal = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// IDs of the jobs currently being processed along with their queue names.
+type RunningJobs = Arc<Mutex<HashMap<Uuid, String>>>;
+
+/// Details on how the [`Worker`] has been stopped.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct StopDetails {
+    /// IDs of the jobs that were still being processed when
+    /// [`WorkerBuilder::shutdown_timeout`] elapsed and so have
+    /// been released back to their queues.
+    pub released_jobs: Vec<Uuid>,
+}
+
+/// Runtime that consumes jobs from queues and dispatches them to registered handlers.
+///
+/// Use [`Client::worker`] to build one.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use std::time::Duration;
+/// use pgboss::{Client, JobDetails};
+///
+/// let c = Client::builder().schema("desired_schema_name").connect().await.unwrap();
+/// c.create_standard_queue("qname").await.unwrap();
+///
+/// let worker = c
+///     .worker()
+///     .register("qname", |job: JobDetails| async move {
+///         println!("processing job {}", job.id);
+///         Ok::<_, std::io::Error>(())
+///     })
+///     .concurrency(10)
+///     .poll_interval(Duration::from_secs(5))
+///     .build();
+///
+/// let _details = worker.run().await.unwrap();
+/// # });
+/// ```
+pub struct Worker {
+    client: Client,
+    handlers: HashMap<String, BoxedHandler>,
+    concurrency: usize,
+    poll_interval: Duration,
+    listen: bool,
+    shutdown_signal: ShutdownSignal,
+    shutdown_timeout: Duration,
+}
+
+impl std::fmt::Debug for Worker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Worker")
+            .field("client", &self.client)
+            .field("queues", &self.handlers.keys().collect::<Vec<_>>())
+            .field("concurrency", &self.concurrency)
+            .field("poll_interval", &self.poll_interval)
+            .field("listen", &self.listen)
+            .field("shutdown_timeout", &self.shutdown_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Worker {
+    /// Start fetching and processing jobs.
+    ///
+    /// Will return [`Error::DoesNotExist`] right away if any of the queues a handler
+    /// has been registered for does not exist. Otherwise, will keep on processing jobs
+    /// until the shutdown signal fires (see [`WorkerBuilder::with_graceful_shutdown`]).
+    /// Errors occurred when fetching jobs are logged and fetching is retried
+    /// after [`WorkerBuilder::poll_interval`].
+    pub async fn run(self) -> Result<StopDetails, Error> {
+        // the shutdown signal is not `Sync`, so we are taking it out
+        // in order not to hold a reference to it across await points
+        let Worker {
+            client,
+            handlers,
+            concurrency,
+            poll_interval,
+            listen,
+            mut shutdown_signal,
+            shutdown_timeout,
+        } = self;
+        for queue_name in handlers.keys() {
+            if client.get_queue(queue_name).await?.is_none() {
+                return Err(Error::DoesNotExist {
+                    msg: "queue does not exist",
+                });
+            }
+        }
+        let listener = match listen {
+            true => Some(client.listen(handlers.keys()).await?),
+            false => None,
+        };
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let running: RunningJobs = Default::default();
+        let mut tasks = JoinSet::new();
+        'outer: loop {
+            let mut drained = true;
+            for (queue_name, handler) in &handlers {
+                let permits = tokio::select! {
+                    biased;
+                    _ = &mut shutdown_signal => break 'outer,
+                    permits = Self::acquire_permits(&semaphore) => permits,
+                };
+                let batch_size = permits.len();
+                let jobs = match client.fetch_jobs(queue_name, batch_size as u64).await {
+                    Ok(jobs) => jobs,
+                    Err(e) => {
+                        log::error!("Failed to fetch jobs from queue {}: {:?}", queue_name, e);
+                        continue;
+                    }
+                };
+                if jobs.len() == batch_size {
+                    drained = false;
+                }
+                for (job, permit) in jobs.into_iter().zip(permits) {
+                    running
+                        .lock()
+                        .expect("not poisoned")
+                        .insert(job.id, job.queue_name.clone());
+                    tasks.spawn(Self::process(
+                        client.clone(),
+                        handler.clone(),
+                        job,
+                        running.clone(),
+                        permit,
+                    ));
+                }
+                while tasks.try_join_next().is_some() {}
+            }
+            if drained {
+                let idle = async {
+                    match &listener {
+                        Some(listener) => {
+                            listener.wait(poll_interval).await;
+                        }
+                        None => tokio::time::sleep(poll_interval).await,
+                    }
+                };
+                tokio::select! {
+                    biased;
+                    _ = &mut shutdown_signal => break 'outer,
+                    _ = idle => {},
+                };
+            }
+        }
+        Self::shutdown(client, tasks, running, shutdown_timeout).await
+    }
+
+    async fn shutdown(
+        client: Client,
+        mut tasks: JoinSet<()>,
+        running: RunningJobs,
+        timeout: Duration,
+    ) -> Result<StopDetails, Error> {
+        let drain = async { while tasks.join_next().await.is_some() {} };
+        if tokio::time::timeout(timeout, drain).await.is_err() {
+            tasks.abort_all();
+            while tasks.join_next().await.is_some() {}
+        }
+        let mut unfinished: HashMap<String, Vec<Uuid>> = HashMap::new();
+        for (job_id, queue_name) in running.lock().expect("not poisoned").drain() {
+            unfinished.entry(queue_name).or_default().push(job_id);
+        }
+        let mut details = StopDetails::default();
+        for (queue_name, job_ids) in unfinished {
+            let released = client.release_jobs(&queue_name, job_ids).await?;
+            details.released_jobs.extend(released);
+        }
+        Ok(details)
+    }
+
+    /// Waits for at least one slot and then grabs all the slots available.
+    async fn acquire_permits(semaphore: &Arc<Semaphore>) -> Vec<OwnedSemaphorePermit> {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("semaphore is never closed");
+        let mut permits = vec![permit];
+        while let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            permits.push(permit);
+        }
+        permits
+    }
+
+    async fn process(
+        client: Client,
+        handler: BoxedHandler,
+        job: JobDetails,
+        running: RunningJobs,
+        _permit: OwnedSemaphorePermit,
+    ) {
+        let queue_name = job.queue_name.clone();
+        let job_id = job.id;
+        // running the handler as a separate task, so that we can catch panics,
+        // making sure it does not outlive this task when aborted on shutdown
+        let handle = tokio::spawn(handler(job));
+        let _guard = AbortOnDrop(handle.abort_handle());
+        let result = match handle.await {
+            Ok(result) => result,
+            Err(e) => Err(e.into()),
+        };
+        let outcome = match result {
+            Ok(output) => client.complete_job(&queue_name, job_id, output).await,
+            Err(e) => {
+                client
+                    .fail_job_with_details(&queue_name, job_id, json!({"message": e.to_string()}))
+                    .await
+            }
+        };
+        if let Err(e) = outcome {
+            log::error!(
+                "Failed to report outcome for job {} from queue {}: {:?}",
+                job_id,
+                queue_name,
+                e
+            );
+        }
+        running.lock().expect("not poisoned").remove(&job_id);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::utils::prepare;
 use chrono::Utc;
-use pgboss::{Job, JobState};
+use pgboss::{Backoff, Job, JobState, RetryOptions};
 use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
 
 #[tokio::test]
 async fn complete_job() {
@@ -107,3 +109,350 @@ async fn fail_job_with_retry() {
     assert_eq!(job_info.retry_count, 1);
     assert_eq!(job_info.state, JobState::Failed);
 }
+
+#[tokio::test]
+async fn release_jobs() {
+    let qname = "jobtype";
+    let c = prepare("release_jobs", qname).await;
+
+    let id1 = c.send_data(qname, json!({"job": 1})).await.unwrap();
+    let id2 = c.send_data(qname, json!({"job": 2})).await.unwrap();
+
+    // only `active` jobs can be released
+    assert!(c.release_jobs(qname, [id1, id2]).await.unwrap().is_empty());
+
+    let jobs = c.fetch_jobs(qname, 2).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+
+    let mut released = c.release_jobs(qname, [id1, id2]).await.unwrap();
+    released.sort();
+    let mut expected = vec![id1, id2];
+    expected.sort();
+    assert_eq!(released, expected);
+
+    let job_info = c.get_job(qname, id1).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Created);
+    assert!(job_info.started_at.is_none());
+
+    // released jobs are visible to consumers right away and
+    // releasing does not count as a retry attempt
+    let jobs = c.fetch_jobs(qname, 2).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert!(jobs.iter().all(|j| j.retry_count == 0));
+
+    assert!(c.release_job(qname, id1).await.unwrap());
+    assert!(!c.release_job(qname, id1).await.unwrap());
+}
+
+#[tokio::test]
+async fn touch_job() {
+    let qname = "jobtype";
+    let c = prepare("touch_job", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+
+    // only `active` jobs can be touched
+    assert!(!c.touch_job(qname, id).await.unwrap());
+
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    let started_at = job.started_at.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(c.touch_job(qname, id).await.unwrap());
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Active);
+    assert!(job_info.started_at.unwrap() > started_at);
+    // touching does not count as a retry attempt
+    assert_eq!(job_info.retry_count, 0);
+
+    assert_eq!(c.touch_jobs(qname, [id, Uuid::new_v4()]).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn heartbeat() {
+    let qname = "jobtype";
+    let c = prepare("heartbeat", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    let started_at = job.started_at.unwrap();
+
+    let heartbeat = c.heartbeat(qname, id, Duration::from_millis(100));
+    assert_eq!(heartbeat.job_id(), id);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let touched_at = c.get_job(qname, id).await.unwrap().unwrap().started_at;
+    assert!(touched_at.unwrap() > started_at);
+
+    // no more touching after the heartbeat has been dropped
+    drop(heartbeat);
+    // in case a touch was in flight when dropping
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let touched_at = c.get_job(qname, id).await.unwrap().unwrap().started_at;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.started_at, touched_at);
+}
+
+#[tokio::test]
+async fn expire_jobs() {
+    let qname = "jobtype";
+    let dlq = "jobtype_dlq";
+    let c = prepare("expire_jobs", qname).await;
+    c.create_standard_queue(dlq).await.unwrap();
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .data(json!({"key": "value"}))
+        .expire_in(Duration::from_secs(1))
+        .retry_limit(1)
+        .dead_letter(dlq)
+        .build();
+    let id = c.send_job(job).await.unwrap();
+    // jobs that are not `active` never expire
+    let untouched = c.send_data(qname, json!({})).await.unwrap();
+    assert_eq!(c.expire_jobs().await.unwrap(), 0);
+
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(job.id, id);
+    // not overdue just yet
+    assert_eq!(c.expire_jobs().await.unwrap(), 0);
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(c.expire_jobs().await.unwrap(), 1);
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Retry);
+    assert_eq!(
+        job_info.output.unwrap(),
+        json!({"message": "job failed by timeout in active state"})
+    );
+
+    // the job is retried and expires again, this time for good
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(job.id, id);
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(c.expire_jobs().await.unwrap(), 1);
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Failed);
+    assert!(job_info.completed_at.is_some());
+
+    // ... and lands in the dead letter queue
+    let dead = c.fetch_job(dlq).await.unwrap().unwrap();
+    assert_eq!(dead.data, json!({"key": "value"}));
+    assert_eq!(
+        dead.output.unwrap(),
+        json!({"message": "job failed by timeout in active state"})
+    );
+
+    let job_info = c.get_job(qname, untouched).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Created);
+}
+
+#[tokio::test]
+async fn retry_failed_and_completed_jobs() {
+    let qname = "jobtype";
+    let c = prepare("retry_failed_and_completed_jobs", qname).await;
+
+    let job = Job::builder().queue_name(qname).retry_limit(1).build();
+    let failed = c.send_job(&job).await.unwrap();
+    for _ in 0..2 {
+        let job = c.fetch_job(qname).await.unwrap().unwrap();
+        assert_eq!(job.id, failed);
+        assert!(c.fail_job(qname, failed).await.unwrap());
+    }
+    let job_details = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Failed);
+    assert_eq!(job_details.retry_count, 1);
+
+    let completed = c.send_job(&job).await.unwrap();
+    c.fetch_job(qname).await.unwrap().unwrap();
+    c.complete_job(qname, completed, json!({})).await.unwrap();
+
+    // completed jobs are not retried by default
+    assert!(!c.retry_job(qname, completed).await.unwrap());
+    assert_eq!(c.retry_jobs(qname, [failed, completed]).await.unwrap(), 1);
+    let job_details = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Created);
+    assert_eq!(job_details.retry_count, 0);
+    assert!(job_details.completed_at.is_none());
+    assert!(job_details.started_at.is_none());
+
+    // the job is given all the attempts once again
+    for _ in 0..2 {
+        let job = c.fetch_job(qname).await.unwrap().unwrap();
+        assert_eq!(job.id, failed);
+        assert!(c.fail_job(qname, failed).await.unwrap());
+    }
+    let job_details = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Failed);
+
+    // preserving retry count, the job only gets one more attempt
+    let opts = RetryOptions::default()
+        .include_completed(true)
+        .reset_retry_count(false);
+    let count = c
+        .retry_jobs_with(qname, [failed, completed, Uuid::new_v4()], opts)
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+    let job_details = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Retry);
+    assert_eq!(job_details.retry_count, 1);
+    let job_details = c.get_job(qname, completed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Created);
+    assert!(job_details.completed_at.is_none());
+
+    let mut jobs = c.fetch_jobs(qname, 2).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    jobs.sort_by_key(|j| j.id != failed);
+    assert_eq!(jobs[0].id, failed);
+    assert!(c.fail_job(qname, failed).await.unwrap());
+    let job_details = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Failed);
+}
+
+#[tokio::test]
+async fn report_progress() {
+    let qname = "jobtype";
+    let c = prepare("report_progress", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+
+    // only active jobs can report progress
+    assert!(!c
+        .report_progress(qname, id, json!({"done": 0}))
+        .await
+        .unwrap());
+    let job_details = c.get_job(qname, id).await.unwrap().unwrap();
+    assert!(job_details.output.is_none());
+
+    c.fetch_job(qname).await.unwrap().unwrap();
+    for done in [10, 42] {
+        assert!(c
+            .report_progress(qname, id, json!({"done": done}))
+            .await
+            .unwrap());
+    }
+    let job_details = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Active);
+    assert_eq!(job_details.output, Some(json!({"done": 42})));
+
+    c.complete_job(qname, id, json!({"done": 100}))
+        .await
+        .unwrap();
+    assert!(!c
+        .report_progress(qname, id, json!({"done": 50}))
+        .await
+        .unwrap());
+    let job_details = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_details.output, Some(json!({"done": 100})));
+}
+
+#[tokio::test]
+async fn fail_job_permanently() {
+    let qname = "jobtype";
+    let c = prepare("fail_job_permanently", qname).await;
+    c.create_standard_queue("jobtype_dlq").await.unwrap();
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .data(json!({"email": "not-an-email"}))
+        .retry_limit(3)
+        .dead_letter("jobtype_dlq")
+        .build();
+    let id = c.send_job(job).await.unwrap();
+
+    // only jobs which are still to be processed can be failed
+    c.fetch_job(qname).await.unwrap().unwrap();
+    let reason = json!({"message": "invalid email"});
+    assert!(c
+        .fail_job_permanently(qname, id, reason.clone())
+        .await
+        .unwrap());
+    assert!(!c
+        .fail_job_permanently(qname, id, reason.clone())
+        .await
+        .unwrap());
+
+    // no retries left for this job ...
+    let job_details = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_details.state, JobState::Failed);
+    assert_eq!(job_details.retry_count, 0);
+    assert_eq!(job_details.output, Some(reason.clone()));
+    assert!(job_details.completed_at.is_some());
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+
+    // ... and it has been sent to the dead letter queue straight away
+    let dead = c.fetch_job("jobtype_dlq").await.unwrap().unwrap();
+    assert_eq!(dead.data, json!({"email": "not-an-email"}));
+    assert_eq!(dead.output, Some(reason));
+}
+
+#[tokio::test]
+async fn retry_with_backoff() {
+    let qname = "jobtype";
+    let c = prepare("retry_with_backoff", qname).await;
+
+    // fails the job and returns the time to wait before it is retried
+    let fail = |id: Uuid| {
+        let c = c.clone();
+        async move {
+            let job = c.fetch_job(qname).await.unwrap().unwrap();
+            assert_eq!(job.id, id);
+            let failed_at = Utc::now();
+            assert!(c.fail_job(qname, id).await.unwrap());
+            let job = c.get_job(qname, id).await.unwrap().unwrap();
+            assert_eq!(job.state, JobState::Retry);
+            (job.start_after - failed_at).to_std().unwrap_or_default()
+        }
+    };
+    let tolerance = Duration::from_millis(500);
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .retry_limit(5)
+        .retry_delay(Duration::from_secs(1))
+        .backoff(Backoff::Linear)
+        .build();
+    let id = c.send_job(job).await.unwrap();
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Linear);
+    let delay = fail(id).await;
+    assert!(delay.abs_diff(Duration::from_secs(1)) < tolerance);
+    tokio::time::sleep(delay).await;
+    let delay = fail(id).await;
+    assert!(delay.abs_diff(Duration::from_secs(2)) < tolerance);
+
+    // 10 to 20 seconds for the first retry attempt if not for the cap
+    let max_delay = Duration::from_secs(5);
+    let job = Job::builder()
+        .queue_name(qname)
+        .retry_delay(Duration::from_secs(10))
+        .backoff(Backoff::CappedExponential { max_delay })
+        .build();
+    let id = c.send_job(job).await.unwrap();
+    let delay = fail(id).await;
+    assert!(delay.abs_diff(max_delay) < tolerance);
+
+    let max_delay = Duration::from_secs(60);
+    let job = Job::builder()
+        .queue_name(qname)
+        .retry_delay(Duration::from_secs(2))
+        .backoff(Backoff::DecorrelatedJitter { max_delay })
+        .build();
+    let id = c.send_job(job).await.unwrap();
+    let delay = fail(id).await;
+    assert!(delay + tolerance > Duration::from_secs(2));
+    assert!(delay < Duration::from_secs(6) + tolerance);
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::DecorrelatedJitter { max_delay });
+
+    // jobs not specifying a strategy are reporting the one implied by `retry_backoff`
+    let id = c.send_data(qname, json!({})).await.unwrap();
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Fixed);
+    let job = Job::builder().queue_name(qname).retry_backoff(true).build();
+    let id = c.send_job(job).await.unwrap();
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Exponential);
+}
@@ -1,12 +1,13 @@
 use crate::utils::{self, prepare};
-use pgboss::{Client, Job};
+use pgboss::{Client, Job, JobState};
+use serde_json::json;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn delete_job_queue_does_not_exist() {
     let schema_name = "delete_job_queue_does_not_exist";
     let qname = "jobtype";
-    utils::drop_schema(&schema_name).await.unwrap();
+    utils::drop_schema(schema_name).await.unwrap();
 
     let c = Client::builder()
         .schema(schema_name)
@@ -28,7 +29,7 @@ async fn delete_job_does_not_exist() {
 #[tokio::test]
 async fn delete_job() {
     let local = "delete_job";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -48,7 +49,7 @@ async fn delete_job() {
 #[tokio::test]
 async fn delete_jobs() {
     let local = "delete_jobs";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -101,7 +102,7 @@ async fn delete_jobs() {
 #[tokio::test]
 async fn delete_jobs_queue_does_not_exist() {
     let local = "delete_jobs_queue_does_not_exist";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
 
@@ -112,7 +113,7 @@ async fn delete_jobs_queue_does_not_exist() {
 #[tokio::test]
 async fn delete_jobs_do_not_exist() {
     let local = "delete_jobs_do_not_exist";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -120,3 +121,42 @@ async fn delete_jobs_do_not_exist() {
     let deleted = c.delete_jobs("jobtype", [Uuid::new_v4()]).await.unwrap();
     assert_eq!(deleted, 0);
 }
+
+#[tokio::test]
+async fn purge_queue() {
+    let qname = "jobtype";
+    let c = prepare("purge_queue", qname).await;
+    c.create_standard_queue("other").await.unwrap();
+
+    let job = Job::builder().queue_name(qname).retry_limit(1).build();
+    let active = c.send_job(&job).await.unwrap();
+    let retried = c.send_job(&job).await.unwrap();
+    let completed = c.send_job(&job).await.unwrap();
+    c.fetch_jobs(qname, 3).await.unwrap();
+    c.fail_job(qname, retried).await.unwrap();
+    c.complete_job(qname, completed, json!({})).await.unwrap();
+    let created1 = c.send_job(&job).await.unwrap();
+    let created2 = c.send_job(&job).await.unwrap();
+    let other = c.send_data("other", json!({})).await.unwrap();
+
+    assert_eq!(c.purge_queue(qname).await.unwrap(), 3);
+    for id in [retried, created1, created2] {
+        assert!(c.get_job(qname, id).await.unwrap().is_none());
+    }
+    // the queue is still there and jobs in other states are left intact
+    assert!(c.get_queue(qname).await.unwrap().is_some());
+    assert!(c.get_job(qname, active).await.unwrap().is_some());
+    assert!(c.get_job(qname, completed).await.unwrap().is_some());
+    assert!(c.get_job("other", other).await.unwrap().is_some());
+    assert_eq!(c.purge_queue(qname).await.unwrap(), 0);
+
+    let purged = c
+        .purge_queue_by_states(qname, [JobState::Completed, JobState::Failed])
+        .await
+        .unwrap();
+    assert_eq!(purged, 1);
+    assert!(c.get_job(qname, completed).await.unwrap().is_none());
+    assert!(c.get_job(qname, active).await.unwrap().is_some());
+
+    assert_eq!(c.purge_queue("does_not_exist").await.unwrap(), 0);
+}
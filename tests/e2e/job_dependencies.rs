@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use crate::utils::prepare;
+use pgboss::{Job, JobState, OnParentFailure};
+use serde_json::json;
+
+#[tokio::test]
+async fn children_wait_for_parents() {
+    let qname = "jobtype";
+    let c = prepare("children_wait_for_parents", qname).await;
+    c.create_standard_queue("transcode").await.unwrap();
+
+    let download1 = c.send_data(qname, json!({"part": 1})).await.unwrap();
+    let download2 = c.send_data(qname, json!({"part": 2})).await.unwrap();
+    let transcode = Job::builder()
+        .queue_name("transcode")
+        .depends_on(download1)
+        .depends_on(download2)
+        .build();
+    let transcode = c.send_job(transcode).await.unwrap();
+    let notify = Job::builder()
+        .queue_name(qname)
+        .depends_on(transcode)
+        .priority(10)
+        .build();
+    let notify = c.send_job(notify).await.unwrap();
+
+    let job = c.get_job("transcode", transcode).await.unwrap().unwrap();
+    assert_eq!(job.parents, vec![download1, download2]);
+    let job = c.get_job(qname, download1).await.unwrap().unwrap();
+    assert!(job.parents.is_empty());
+
+    let children = c.get_child_jobs(download1).await.unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].id, transcode);
+    assert_eq!(children[0].queue_name, "transcode");
+    assert_eq!(c.get_child_jobs(transcode).await.unwrap()[0].id, notify);
+    assert!(c.get_child_jobs(notify).await.unwrap().is_empty());
+
+    // despite its priority, the child cannot be fetched just yet
+    let jobs = c.fetch_jobs(qname, 10).await.unwrap();
+    assert_eq!(jobs.len(), 2);
+    assert!(jobs.iter().all(|j| j.id != notify));
+    c.complete_job(qname, download1, json!({})).await.unwrap();
+    assert!(c.fetch_job("transcode").await.unwrap().is_none());
+
+    // all parents completed
+    c.complete_job(qname, download2, json!({})).await.unwrap();
+    let job = c.fetch_job("transcode").await.unwrap().unwrap();
+    assert_eq!(job.id, transcode);
+    assert_eq!(job.parents.len(), 2);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+    c.complete_job("transcode", transcode, json!({}))
+        .await
+        .unwrap();
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(job.id, notify);
+}
+
+#[tokio::test]
+async fn parent_failure_propagates_to_children() {
+    let qname = "jobtype";
+    let c = prepare("parent_failure_propagates_to_children", qname).await;
+
+    let parent = Job::builder().queue_name(qname).retry_limit(1).build();
+    let parent = c.send_job(parent).await.unwrap();
+    let cancelled = Job::builder().queue_name(qname).depends_on(parent).build();
+    let cancelled = c.send_job(cancelled).await.unwrap();
+    let failed = Job::builder()
+        .queue_name(qname)
+        .depends_on(parent)
+        .on_parent_failure(OnParentFailure::Fail)
+        .build();
+    let failed = c.send_job(failed).await.unwrap();
+    let grandchild = Job::builder()
+        .queue_name(qname)
+        .depends_on(cancelled)
+        .build();
+    let grandchild = c.send_job(grandchild).await.unwrap();
+
+    // children keep waiting while the parent is being retried ...
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, parent);
+    c.fail_job(qname, parent).await.unwrap();
+    let job = c.get_job(qname, parent).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Retry);
+    let job = c.get_job(qname, cancelled).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Created);
+    assert_eq!(job.parents, vec![parent]);
+
+    // ... but not once it has failed for good
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, parent);
+    c.fail_job(qname, parent).await.unwrap();
+    let job = c.get_job(qname, parent).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    let job = c.get_job(qname, cancelled).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+    assert!(job.completed_at.is_some());
+    let job = c.get_job(qname, failed).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    assert_eq!(job.output, Some(json!({"message": "parent job failed"})));
+    let job = c.get_job(qname, grandchild).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+
+    // jobs depending on a failed job will never run
+    let late = Job::builder().queue_name(qname).depends_on(parent).build();
+    let late = c.send_job(late).await.unwrap();
+    let job = c.get_job(qname, late).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+}
+
+#[tokio::test]
+async fn grandchild_of_failed_job_never_runs() {
+    let qname = "jobtype";
+    let c = prepare("grandchild_of_failed_job_never_runs", qname).await;
+
+    let parent = Job::builder().queue_name(qname).retry_limit(0).build();
+    let parent = c.send_job(parent).await.unwrap();
+    let child = Job::builder().queue_name(qname).depends_on(parent).build();
+    let child = c.send_job(child).await.unwrap();
+
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, parent);
+    c.fail_job(qname, parent).await.unwrap();
+    let job = c.get_job(qname, child).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+
+    // the cancelled child does not release its own children
+    let grandchild = Job::builder().queue_name(qname).depends_on(child).build();
+    let grandchild = c.send_job(grandchild).await.unwrap();
+    let job = c.get_job(qname, grandchild).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn cancelled_parent_propagates_to_children() {
+    let qname = "jobtype";
+    let c = prepare("cancelled_parent_propagates_to_children", qname).await;
+
+    let parent = c.send_data(qname, json!({})).await.unwrap();
+    let child = Job::builder()
+        .queue_name(qname)
+        .depends_on(parent)
+        .on_parent_failure(OnParentFailure::Fail)
+        .build();
+    let child = c.send_job(child).await.unwrap();
+    let grandchild = Job::builder().queue_name(qname).depends_on(child).build();
+    let grandchild = c.send_job(grandchild).await.unwrap();
+
+    c.cancel_job(qname, parent).await.unwrap();
+    let job = c.get_job(qname, child).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    assert_eq!(job.output, Some(json!({"message": "parent job cancelled"})));
+    let job = c.get_job(qname, grandchild).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn archived_parent_releases_children() {
+    let qname = "jobtype";
+    let c = prepare("archived_parent_releases_children", qname).await;
+
+    let parent = Job::builder()
+        .queue_name(qname)
+        .retain_for(Duration::from_millis(100))
+        .build();
+    let parent = c.send_job(parent).await.unwrap();
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, parent);
+    c.complete_job(qname, parent, json!({})).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(c.archive_jobs().await.unwrap(), 1);
+
+    let child = Job::builder().queue_name(qname).depends_on(parent).build();
+    let child = c.send_job(child).await.unwrap();
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, child);
+}
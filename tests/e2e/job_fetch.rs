@@ -1,14 +1,15 @@
 use std::time::Duration;
 
 use crate::utils;
-use pgboss::{Client, Job, JobState};
+use pgboss::{Client, Error, Job, JobState};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
 #[tokio::test]
 async fn fetch_one_job() {
     let local = "fetch_one_job";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -103,7 +104,7 @@ async fn fetch_one_job() {
 #[tokio::test]
 async fn fetch_many_jobs() {
     let local = "fetch_many_jobs";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -155,3 +156,122 @@ async fn fetch_many_jobs() {
     // queue has been drained!
     assert!(c.fetch_job("jobtype").await.expect("no error").is_none());
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Resize {
+    width: u32,
+    height: u32,
+}
+
+#[tokio::test]
+async fn fetch_typed_job() {
+    let qname = "jobtype";
+    let c = crate::utils::prepare("fetch_typed_job", qname).await;
+
+    let payload = Resize {
+        width: 640,
+        height: 480,
+    };
+    let id = c.send_data(qname, &payload).await.unwrap();
+
+    let job = c
+        .fetch_typed_job::<Resize, _>(qname)
+        .await
+        .expect("no error")
+        .expect("a job");
+    assert_eq!(job.id, id);
+    assert_eq!(job.data, payload);
+    assert_eq!(job.state, JobState::Active);
+
+    // queue has been drained!
+    assert!(c
+        .fetch_typed_job::<Resize, _>(qname)
+        .await
+        .expect("no error")
+        .is_none());
+}
+
+#[tokio::test]
+async fn fetch_typed_job_malformed_payload() {
+    let qname = "jobtype";
+    let c = crate::utils::prepare("fetch_typed_job_malformed_payload", qname).await;
+
+    let id = c.send_data(qname, json!({"width": 640})).await.unwrap();
+
+    let err = c.fetch_typed_job::<Resize, _>(qname).await.unwrap_err();
+    if let Error::Payload {
+        job_id, queue_name, ..
+    } = &err
+    {
+        assert_eq!(*job_id, id);
+        assert_eq!(queue_name, qname);
+        assert!(err.to_string().contains("missing field `height`"));
+    } else {
+        unreachable!()
+    }
+
+    // the job has been consumed and so we can fail it
+    assert!(c
+        .fail_job_with_details(qname, id, json!({"message": err.to_string()}))
+        .await
+        .unwrap());
+
+    // raw jobs can be converted as well
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert!(job.try_into_typed::<Resize>().is_err());
+}
+
+#[tokio::test]
+async fn get_many_jobs() {
+    let local = "get_many_jobs";
+    let qname = "jobtype";
+    let c = utils::prepare(local, qname).await;
+    c.create_standard_queue("other").await.unwrap();
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .retain_for(Duration::from_millis(100))
+        .build();
+    let archived = c.send_job(&job).await.unwrap();
+    let first = c.send_data(qname, json!({"n": 1})).await.unwrap();
+    let second = c.send_data(qname, json!({"n": 2})).await.unwrap();
+    let elsewhere = c.send_data("other", json!({})).await.unwrap();
+    let unknown = Uuid::new_v4();
+
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(job.id, archived);
+    c.complete_job(qname, archived, json!({})).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(c.archive_jobs().await.unwrap(), 1);
+
+    // order of ids is preserved, unknown ids are skipped
+    let ids = [second, unknown, elsewhere, first, archived];
+    let jobs = c.get_jobs(qname, ids).await.unwrap();
+    assert_eq!(
+        jobs.iter().map(|j| j.id).collect::<Vec<_>>(),
+        vec![second, first]
+    );
+    assert_eq!(jobs[0].data, json!({"n": 2}));
+    assert_eq!(jobs[0].state, JobState::Created);
+
+    let jobs = c.get_jobs_with_archive(qname, ids).await.unwrap();
+    assert_eq!(
+        jobs.iter().map(|j| j.id).collect::<Vec<_>>(),
+        vec![second, first, archived]
+    );
+    assert_eq!(jobs[2].state, JobState::Completed);
+
+    // across queues
+    let jobs = c.get_jobs_by_ids(ids).await.unwrap();
+    assert_eq!(
+        jobs.iter().map(|j| j.id).collect::<Vec<_>>(),
+        vec![second, elsewhere, first]
+    );
+    assert_eq!(jobs[1].queue_name, "other");
+
+    let jobs = c.get_jobs_by_ids_with_archive(ids).await.unwrap();
+    assert_eq!(jobs.len(), 4);
+    assert_eq!(jobs[3].id, archived);
+
+    assert!(c.get_jobs(qname, []).await.unwrap().is_empty());
+}
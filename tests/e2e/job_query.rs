@@ -0,0 +1,132 @@
+use crate::utils;
+use chrono::Utc;
+use pgboss::{Job, JobCursor, JobState};
+use serde_json::json;
+
+#[tokio::test]
+async fn query_jobs() {
+    let qname = "jobtype";
+    let c = utils::prepare("query_jobs", qname).await;
+    c.create_standard_queue("other").await.unwrap();
+    c.create_standard_queue("dlq").await.unwrap();
+
+    let start = Utc::now();
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let job = Job::builder()
+            .queue_name(qname)
+            .data(json!({"tenant": if i % 2 == 0 { "acme" } else { "umbrella" }, "n": i}))
+            .build();
+        ids.push(c.send_job(job).await.unwrap());
+    }
+    let keyed = Job::builder()
+        .queue_name(qname)
+        .singleton_key("buzz")
+        .dead_letter("dlq")
+        .build();
+    let keyed = c.send_job(keyed).await.unwrap();
+    let elsewhere = c
+        .send_data("other", json!({"tenant": "acme"}))
+        .await
+        .unwrap();
+    let active = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(active.id, ids[0]);
+
+    // everything, in creation order
+    let jobs = c.query_jobs().fetch().await.unwrap();
+    assert_eq!(jobs.len(), 7);
+    assert_eq!(jobs[0].id, ids[0]);
+    assert_eq!(jobs[6].id, elsewhere);
+
+    let jobs = c.query_jobs().queue_name("other").fetch().await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, elsewhere);
+
+    let jobs = c
+        .query_jobs()
+        .queue_name(qname)
+        .state(JobState::Active)
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, ids[0]);
+    let jobs = c
+        .query_jobs()
+        .state(JobState::Active)
+        .state(JobState::Created)
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 7);
+    let jobs = c
+        .query_jobs()
+        .state(JobState::Failed)
+        .fetch()
+        .await
+        .unwrap();
+    assert!(jobs.is_empty());
+
+    let jobs = c.query_jobs().singleton_key("buzz").fetch().await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, keyed);
+    let jobs = c.query_jobs().dead_letter("dlq").fetch().await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, keyed);
+
+    let jobs = c
+        .query_jobs()
+        .queue_name(qname)
+        .data_contains(json!({"tenant": "acme"}))
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(
+        jobs.iter().map(|j| j.id).collect::<Vec<_>>(),
+        vec![ids[0], ids[2], ids[4]]
+    );
+
+    let jobs = c.query_jobs().created_before(start).fetch().await.unwrap();
+    assert!(jobs.is_empty());
+    let jobs = c
+        .query_jobs()
+        .created_after(start)
+        .created_before(Utc::now())
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(jobs.len(), 7);
+
+    // keyset pagination
+    let mut query = c.query_jobs().queue_name(qname).limit(2);
+    let mut pages = Vec::new();
+    loop {
+        let jobs = query.fetch().await.unwrap();
+        let Some(last) = jobs.last() else {
+            break;
+        };
+        query = query.after(last);
+        pages.push(jobs.iter().map(|j| j.id).collect::<Vec<_>>());
+    }
+    assert_eq!(
+        pages,
+        vec![
+            vec![ids[0], ids[1]],
+            vec![ids[2], ids[3]],
+            vec![ids[4], keyed]
+        ]
+    );
+
+    // cursors can be passed around
+    let cursor: JobCursor =
+        serde_json::from_value(serde_json::to_value(JobCursor::from(&active)).unwrap()).unwrap();
+    let jobs = c
+        .query_jobs()
+        .queue_name(qname)
+        .after(cursor)
+        .limit(1)
+        .fetch()
+        .await
+        .unwrap();
+    assert_eq!(jobs[0].id, ids[1]);
+}
@@ -9,7 +9,7 @@ use tokio::time;
 #[tokio::test]
 async fn send_job() {
     let local = "send_job";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -20,7 +20,7 @@ async fn send_job() {
 #[tokio::test]
 async fn send_job_with_id() {
     let local = "send_job_with_id";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -42,7 +42,7 @@ async fn send_job_with_id() {
 #[tokio::test]
 async fn send_job_with_dead_letter() {
     let local = "send_job_with_dead_letter";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -64,7 +64,7 @@ async fn send_job_with_dead_letter() {
 #[tokio::test]
 async fn send_job_with_dead_letter_does_not_exist() {
     let local = "send_job_with_dead_letter_does_not_exist";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -86,7 +86,7 @@ async fn send_job_with_dead_letter_does_not_exist() {
 #[tokio::test]
 async fn send_job_queue_does_not_exist() {
     let local = "send_job_queue_does_not_exist";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     let job = Job::builder().queue_name("jobtype").build();
@@ -100,7 +100,7 @@ async fn send_job_queue_does_not_exist() {
 #[tokio::test]
 async fn send_data() {
     let local = "send_data";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -111,7 +111,7 @@ async fn send_data() {
 #[tokio::test]
 async fn send_data_queue_does_not_exist() {
     let local = "send_data_queue_does_not_exist";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
 
@@ -129,7 +129,7 @@ async fn send_data_queue_does_not_exist() {
 #[tokio::test]
 async fn send_job_fully_customized() {
     let local = "send_job_fully_customized";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -205,7 +205,7 @@ async fn send_job_fully_customized() {
 #[tokio::test]
 async fn send_jobs_throttled() {
     let local = "send_jobs_throttled";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -239,7 +239,7 @@ async fn send_jobs_throttled() {
 #[tokio::test]
 async fn send_job_dlq_named_as_main_queue() {
     let local = "send_job_dlq_named_as_main_queue";
-    utils::drop_schema(&local).await.unwrap();
+    utils::drop_schema(local).await.unwrap();
 
     let c = Client::builder().schema(local).connect().await.unwrap();
     c.create_standard_queue("jobtype").await.unwrap();
@@ -305,3 +305,219 @@ async fn send_job_dlq_named_as_main_queue() {
         job2_from_dlq.output.unwrap()
     );
 }
+
+#[tokio::test]
+async fn send_jobs_in_bulk() {
+    let local = "send_jobs_in_bulk";
+    utils::drop_schema(local).await.unwrap();
+
+    let c = Client::builder().schema(local).connect().await.unwrap();
+    let queue = pgboss::Queue::builder()
+        .name("jobtype")
+        .retry_limit(7)
+        .build();
+    c.create_queue(&queue).await.unwrap();
+    c.create_standard_queue("other").await.unwrap();
+
+    assert!(c.send_jobs::<Job>(&[]).await.unwrap().is_empty());
+
+    let id = uuid::Uuid::new_v4();
+    let jobs = vec![
+        Job::builder()
+            .queue_name("jobtype")
+            .data(json!({"n": 1}))
+            .build(),
+        Job::builder()
+            .queue_name("other")
+            .data(json!({"n": 2}))
+            .build(),
+        Job::builder()
+            .id(id)
+            .queue_name("jobtype")
+            .data(json!({"n": 3}))
+            .priority(10)
+            .retry_limit(1)
+            .build(),
+    ];
+    let ids = c.send_jobs(&jobs).await.unwrap();
+    assert_eq!(ids.len(), 3);
+    assert_eq!(ids[2], id);
+
+    let job = c.get_job("jobtype", ids[0]).await.unwrap().unwrap();
+    assert_eq!(job.data, json!({"n": 1}));
+    // queue defaults are applied ...
+    assert_eq!(job.retry_limit, 7);
+    let job = c.get_job("other", ids[1]).await.unwrap().unwrap();
+    assert_eq!(job.data, json!({"n": 2}));
+    assert_eq!(job.retry_limit, 2);
+    // ... unless overridden
+    let job = c.get_job("jobtype", id).await.unwrap().unwrap();
+    assert_eq!(job.data, json!({"n": 3}));
+    assert_eq!(job.priority, 10);
+    assert_eq!(job.retry_limit, 1);
+}
+
+#[tokio::test]
+async fn send_jobs_in_bulk_all_or_nothing() {
+    let local = "send_jobs_in_bulk_all_or_nothing";
+    utils::drop_schema(local).await.unwrap();
+
+    let c = Client::builder().schema(local).connect().await.unwrap();
+    c.create_standard_queue("jobtype").await.unwrap();
+
+    let jobs = [
+        Job::builder().queue_name("jobtype").build(),
+        Job::builder().queue_name("does_not_exist").build(),
+    ];
+    let err = c.send_jobs(&jobs).await.unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "queue does not exist");
+    } else {
+        unreachable!()
+    }
+
+    let id = uuid::Uuid::new_v4();
+    let jobs = [
+        Job::builder().queue_name("jobtype").build(),
+        Job::builder().id(id).queue_name("jobtype").build(),
+        Job::builder().id(id).queue_name("jobtype").build(),
+    ];
+    let err = c.send_jobs(&jobs).await.unwrap_err();
+    if let Error::Conflict { msg } = err {
+        assert_eq!(msg, "job with this id already exists");
+    } else {
+        unreachable!()
+    }
+
+    assert_eq!(
+        c.get_queue_size("jobtype", JobState::Active).await.unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn send_jobs_in_transaction() {
+    let qname = "jobtype";
+    let c = utils::prepare("send_jobs_in_transaction", qname).await;
+    let pool = sqlx::PgPool::connect(&utils::POSRGRES_URL).await.unwrap();
+
+    // rolled back along with the caller's transaction
+    let mut tx = pool.begin().await.unwrap();
+    let id = c.send_data_in(&mut *tx, qname, json!({})).await.unwrap();
+    let job = Job::builder().queue_name(qname).build();
+    let ids = c.send_jobs_in(&mut *tx, &[&job, &job]).await.unwrap();
+    assert_eq!(ids.len(), 2);
+    tx.rollback().await.unwrap();
+    assert!(c.get_job(qname, id).await.unwrap().is_none());
+    assert!(c.get_job(qname, ids[0]).await.unwrap().is_none());
+
+    // a failed bulk insert does not poison the caller's transaction
+    let mut tx = pool.begin().await.unwrap();
+    let id = c.send_job_in(&mut *tx, &job).await.unwrap();
+    let bad = Job::builder().queue_name("does_not_exist").build();
+    let err = c.send_jobs_in(&mut *tx, &[&job, &bad]).await.unwrap_err();
+    assert!(matches!(err, Error::DoesNotExist { .. }));
+    assert!(c.get_job(qname, id).await.unwrap().is_none());
+    tx.commit().await.unwrap();
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Created);
+
+    // state changes are visible only once committed
+    let fetched = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(fetched.id, id);
+    let mut tx = pool.begin().await.unwrap();
+    let count = c
+        .complete_jobs_in(&mut *tx, qname, [id], json!({"ok": true}))
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Active);
+    tx.commit().await.unwrap();
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Completed);
+
+    // and also accepts a pool
+    let id = c.send_data_in(&pool, qname, json!({})).await.unwrap();
+    assert_eq!(c.cancel_jobs_in(&pool, qname, [id]).await.unwrap(), 1);
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Cancelled);
+}
+
+#[tokio::test]
+async fn send_throttled_and_debounced() {
+    let qname = "jobtype";
+    let c = utils::prepare("send_throttled_and_debounced", qname).await;
+    // long enough for the test not to cross a slot boundary
+    let period = Duration::from_secs(60 * 60);
+
+    let id = c
+        .send_throttled(qname, json!({"n": 1}), period, None)
+        .await
+        .unwrap();
+    let err = c
+        .send_throttled(qname, json!({"n": 2}), period, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Throttled { .. }));
+    // slots are per key
+    let keyed = c
+        .send_throttled(qname, json!({"n": 3}), period, Some("buzz"))
+        .await
+        .unwrap();
+    assert_ne!(id, keyed);
+
+    // current slot is taken, so the job goes to the next one ...
+    let before_sent = Utc::now();
+    let debounced = c
+        .send_debounced(qname, json!({"n": 4}), period, None)
+        .await
+        .unwrap();
+    let job = c.get_job(qname, debounced).await.unwrap().unwrap();
+    let period_secs = period.as_secs() as i64;
+    let next_slot = (before_sent.timestamp() / period_secs + 1) * period_secs;
+    assert_eq!(job.start_after.timestamp(), next_slot);
+    assert_eq!(job.start_after.timestamp_subsec_nanos(), 0);
+    assert_eq!(job.data, json!({"n": 4}));
+
+    // ... unless that one is taken too
+    let err = c
+        .send_debounced(qname, json!({"n": 5}), period, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Throttled { .. }));
+
+    // the slot is free, so the job is not deferred
+    let debounced = c
+        .send_debounced(qname, json!({"n": 6}), period, Some("fizz"))
+        .await
+        .unwrap();
+    let job = c.get_job(qname, debounced).await.unwrap().unwrap();
+    assert!(job.start_after <= Utc::now());
+
+    let err = c
+        .send_throttled("does_not_exist", json!({}), period, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::DoesNotExist { .. }));
+}
+
+#[tokio::test]
+async fn send_throttled_sub_second_period() {
+    let qname = "jobtype";
+    let c = utils::prepare("send_throttled_sub_second_period", qname).await;
+    let period = Duration::from_millis(500);
+
+    // periods shorter than a second are treated as one second
+    let before_sent = Utc::now();
+    c.send_throttled(qname, json!({"n": 1}), period, None)
+        .await
+        .unwrap();
+    let res = c.send_throttled(qname, json!({"n": 2}), period, None).await;
+    if Utc::now().timestamp() == before_sent.timestamp() {
+        assert!(matches!(res, Err(Error::Throttled { .. })));
+    }
+    c.send_debounced(qname, json!({"n": 3}), Duration::ZERO, Some("fizz"))
+        .await
+        .unwrap();
+}
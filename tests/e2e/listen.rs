@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use crate::utils::{self, prepare};
+use pgboss::{Client, Error, Job, JobDetails, JobState};
+use serde_json::json;
+use sqlx::postgres::PgPoolOptions;
+
+async fn prepare_notifying(schema: &str, qname: &str) -> Client {
+    utils::drop_schema(schema).await.unwrap();
+    let c = Client::builder()
+        .schema(schema)
+        .notify(true)
+        .connect()
+        .await
+        .unwrap();
+    c.create_standard_queue(qname).await.unwrap();
+    c
+}
+
+#[tokio::test]
+async fn listener_woken_up_on_send() {
+    let qname = "jobtype";
+    let c = prepare_notifying("listener_woken_up_on_send", qname).await;
+    c.create_standard_queue("other").await.unwrap();
+
+    let listener = c.listen([qname]).await.unwrap();
+
+    // no jobs sent just yet
+    assert!(!listener.wait(Duration::from_millis(100)).await);
+
+    // jobs sent to other queues are not reported
+    c.send_data("other", json!({})).await.unwrap();
+    assert!(!listener.wait(Duration::from_millis(500)).await);
+
+    let producer = c.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        producer.send_data(qname, json!({})).await.unwrap();
+    });
+    assert!(listener.wait(Duration::from_secs(10)).await);
+    assert!(c.fetch_job(qname).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn listener_not_woken_up_for_deferred_job() {
+    let qname = "jobtype";
+    let c = prepare_notifying("listener_not_woken_up_for_deferred_job", qname).await;
+
+    let listener = c.listen([qname]).await.unwrap();
+    let job = Job::builder()
+        .queue_name(qname)
+        .delay_for(Duration::from_secs(60))
+        .build();
+    c.send_job(&job).await.unwrap();
+    assert!(!listener.wait(Duration::from_millis(500)).await);
+}
+
+#[tokio::test]
+async fn listener_not_woken_up_if_notify_disabled() {
+    let qname = "jobtype";
+    let c = prepare("listener_not_woken_up_if_notify_disabled", qname).await;
+
+    let listener = c.listen([qname]).await.unwrap();
+    c.send_data(qname, json!({})).await.unwrap();
+    assert!(!listener.wait(Duration::from_millis(500)).await);
+}
+
+#[tokio::test]
+async fn listener_does_not_take_connection_from_pool() {
+    let local = "listener_does_not_take_connection_from_pool";
+    let qname = "jobtype";
+    utils::drop_schema(local).await.unwrap();
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&utils::POSRGRES_URL)
+        .await
+        .unwrap();
+    let c = Client::builder()
+        .schema(local)
+        .notify(true)
+        .with_pool(pool)
+        .await
+        .unwrap();
+    c.create_standard_queue(qname).await.unwrap();
+
+    let listener = c.listen([qname]).await.unwrap();
+    let sending = c.send_data(qname, json!({}));
+    tokio::time::timeout(Duration::from_secs(5), sending)
+        .await
+        .expect("pool not exhausted")
+        .unwrap();
+    assert!(listener.wait(Duration::from_secs(5)).await);
+}
+
+#[tokio::test]
+async fn worker_woken_up_by_listener() {
+    let qname = "jobtype";
+    let c = prepare_notifying("worker_woken_up_by_listener", qname).await;
+
+    let worker = c
+        .worker()
+        .register(qname, |_job: JobDetails| async { Ok::<_, Error>(()) })
+        // way beyond the time we are going to wait for the job to be completed
+        .poll_interval(Duration::from_secs(60))
+        .listen(true)
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    // let the worker drain the queue and start waiting
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let id = c.send_data(qname, json!({})).await.unwrap();
+
+    let mut state = JobState::Created;
+    for _ in 0..50 {
+        state = c.get_job(qname, id).await.unwrap().unwrap().state;
+        if state == JobState::Completed {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(state, JobState::Completed);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn wait_for_job_notified() {
+    let qname = "jobtype";
+    let c = prepare_notifying("wait_for_job_notified", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+    assert!(matches!(
+        c.wait_for_job(qname, uuid::Uuid::new_v4(), Duration::from_secs(1))
+            .await
+            .unwrap_err(),
+        Error::DoesNotExist { .. }
+    ));
+
+    // not done just yet
+    let job = c
+        .wait_for_job(qname, id, Duration::from_millis(200))
+        .await
+        .unwrap();
+    assert!(job.is_none());
+
+    let consumer = c.clone();
+    tokio::spawn(async move {
+        let job = consumer.fetch_job(qname).await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        consumer
+            .complete_job(qname, job.id, json!({"result": 42}))
+            .await
+            .unwrap();
+    });
+    let started = std::time::Instant::now();
+    let job = c
+        .wait_for_job(qname, id, Duration::from_secs(30))
+        .await
+        .unwrap()
+        .unwrap();
+    // woken up by the notification rather than polling
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert_eq!(job.state, JobState::Completed);
+    assert_eq!(job.output, Some(json!({"result": 42})));
+
+    // failed jobs are done too
+    let job = Job::builder().queue_name(qname).retry_limit(0).build();
+    let id = c.send_job(job).await.unwrap();
+    let consumer = c.clone();
+    tokio::spawn(async move {
+        consumer.fetch_job(qname).await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        consumer.fail_job(qname, id).await.unwrap();
+    });
+    let started = std::time::Instant::now();
+    let job = c
+        .wait_for_job(qname, id, Duration::from_secs(30))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert_eq!(job.state, JobState::Failed);
+}
+
+#[tokio::test]
+async fn wait_for_job_polling() {
+    let qname = "jobtype";
+    let c = prepare("wait_for_job_polling", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+    let consumer = c.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        consumer.cancel_job(qname, id).await.unwrap();
+    });
+    let job = c
+        .wait_for_job(qname, id, Duration::from_secs(10))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.state, JobState::Cancelled);
+}
+
+#[tokio::test]
+async fn wait_for_many_jobs_with_small_pool() {
+    let local = "wait_for_many_jobs_with_small_pool";
+    let qname = "jobtype";
+    utils::drop_schema(local).await.unwrap();
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&utils::POSRGRES_URL)
+        .await
+        .unwrap();
+    let c = Client::builder()
+        .schema(local)
+        .notify(true)
+        .with_pool(pool)
+        .await
+        .unwrap();
+    c.create_standard_queue(qname).await.unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..6 {
+        ids.push(c.send_data(qname, json!({"i": i})).await.unwrap());
+    }
+    let mut waits = tokio::task::JoinSet::new();
+    for id in ids.clone() {
+        let c = c.clone();
+        waits.spawn(async move { c.wait_for_job(qname, id, Duration::from_secs(30)).await });
+    }
+    // let all of them start waiting
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let started = std::time::Instant::now();
+    let jobs = c.fetch_jobs(qname, 10).await.unwrap();
+    assert_eq!(jobs.len(), ids.len());
+    let job_ids: Vec<_> = jobs.iter().map(|j| j.id).collect();
+    c.complete_jobs(qname, job_ids, json!({"ok": true}))
+        .await
+        .unwrap();
+    while let Some(job) = waits.join_next().await {
+        let job = job.unwrap().unwrap().unwrap();
+        assert_eq!(job.state, JobState::Completed);
+    }
+    // woken up by the notifications rather than polling
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
@@ -1,6 +1,13 @@
 mod job_change_state;
 mod job_delete;
+mod job_dependencies;
 mod job_fetch;
+mod job_query;
 mod job_send;
+mod listen;
+mod maintenance;
+mod pubsub;
 mod queue;
+mod schedule;
 mod utils;
+mod worker;
@@ -0,0 +1,124 @@
+use crate::utils::{self, prepare};
+use pgboss::{Job, JobState, MaintenanceDetails, MaintenanceOutcome};
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn archive_jobs() {
+    let qname = "jobtype";
+    let c = prepare("archive_jobs", qname).await;
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .retain_for(Duration::from_millis(500))
+        .build();
+    let completed = c.send_job(&job).await.unwrap();
+    let created = c.send_job(&job).await.unwrap();
+    let long_lived = c.send_data(qname, json!({})).await.unwrap();
+
+    let mut jobs = c.fetch_jobs(qname, 3).await.unwrap();
+    jobs.sort_by_key(|j| j.id != completed);
+    assert_eq!(jobs[0].id, completed);
+    c.complete_job(qname, completed, json!({})).await.unwrap();
+    c.complete_job(qname, long_lived, json!({})).await.unwrap();
+    c.release_job(qname, created).await.unwrap();
+
+    // still retained
+    assert_eq!(c.archive_jobs().await.unwrap(), 0);
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(c.archive_jobs().await.unwrap(), 1);
+    assert!(c.get_job(qname, completed).await.unwrap().is_none());
+
+    // jobs which are still to be processed are not archived,
+    // just like those not past their retention period
+    assert!(c.get_job(qname, created).await.unwrap().is_some());
+    assert!(c.get_job(qname, long_lived).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn maintain() {
+    let local = "maintain";
+    let qname = "jobtype";
+    let c = prepare(local, qname).await;
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .retain_for(Duration::from_millis(100))
+        .build();
+    let id = c.send_job(&job).await.unwrap();
+    c.fetch_job(qname).await.unwrap().unwrap();
+    c.cancel_job(qname, id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let delete_after = Duration::from_secs(60 * 60);
+    let details = c.maintain(delete_after).await.unwrap();
+    assert_eq!(details.expired_jobs, 0);
+    assert_eq!(details.archived_jobs, 1);
+    assert_eq!(details.deleted_jobs, 0);
+
+    // pretend the job was archived a while ago
+    utils::ad_hoc_sql([format!(
+        "UPDATE {local}.archive SET archived_on = now() - interval '2 hours';"
+    )])
+    .await
+    .unwrap();
+    let details = c.maintain(delete_after).await.unwrap();
+    assert_eq!(details.deleted_jobs, 1);
+    assert_eq!(details.archived_jobs, 0);
+    assert_eq!(
+        c.maintain(delete_after).await.unwrap(),
+        MaintenanceDetails::default()
+    );
+}
+
+#[tokio::test]
+async fn supervisors_take_turns() {
+    let qname = "jobtype";
+    let c = prepare("supervisors_take_turns", qname).await;
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .expire_in(Duration::from_secs(1))
+        .retry_limit(0)
+        .build();
+    let id = c.send_job(job).await.unwrap();
+    c.fetch_job(qname).await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    let interval = Duration::from_secs(2);
+    let supervisor1 = c.supervisor().interval(interval).start();
+    let supervisor2 = c.supervisor().interval(interval).start();
+    assert_eq!(supervisor1.interval(), interval);
+    assert_eq!(
+        supervisor1.delete_after(),
+        Duration::from_secs(60 * 60 * 24 * 7)
+    );
+
+    let mut runs = Vec::new();
+    for _ in 0..50 {
+        if let (Some(run1), Some(run2)) = (supervisor1.last_run(), supervisor2.last_run()) {
+            runs = vec![run1, run2];
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(runs.len(), 2);
+    assert!(runs.iter().all(|run| run.started_at <= run.finished_at));
+
+    // only one of the instances has maintained the system
+    let performed: Vec<_> = runs
+        .iter()
+        .filter_map(|run| match &run.outcome {
+            MaintenanceOutcome::Performed(details) => Some(details.clone()),
+            MaintenanceOutcome::Skipped => None,
+            MaintenanceOutcome::Failed(e) => panic!("unexpected error: {:?}", e),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(performed.len(), 1);
+    assert_eq!(performed[0].expired_jobs, 1);
+
+    let job_info = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job_info.state, JobState::Failed);
+}
@@ -0,0 +1,63 @@
+use crate::utils::prepare;
+use pgboss::{Error, Job};
+use serde_json::json;
+
+#[tokio::test]
+async fn publish_to_subscribed_queues() {
+    let event = "user_signed_up";
+    let c = prepare("publish_to_subscribed_queues", "emails").await;
+    c.create_standard_queue("analytics").await.unwrap();
+    c.create_standard_queue("billing").await.unwrap();
+
+    // no subscribers yet
+    assert!(c.publish_data(event, json!({})).await.unwrap().is_empty());
+
+    c.subscribe(event, "emails").await.unwrap();
+    c.subscribe(event, "analytics").await.unwrap();
+    // subscribing twice is fine
+    c.subscribe(event, "analytics").await.unwrap();
+    c.subscribe("user_deleted", "billing").await.unwrap();
+    assert_eq!(
+        c.get_subscribed_queues(event).await.unwrap(),
+        vec!["analytics", "emails"]
+    );
+
+    let job = Job::builder()
+        .data(json!({"user_id": 1}))
+        .priority(10)
+        .retry_limit(5)
+        .build();
+    let ids = c.publish(event, &job).await.unwrap();
+    assert_eq!(ids.len(), 2);
+
+    for (qname, id) in ["analytics", "emails"].into_iter().zip(ids) {
+        let job = c.fetch_job(qname).await.unwrap().unwrap();
+        assert_eq!(job.id, id);
+        assert_eq!(job.data, json!({"user_id": 1}));
+        assert_eq!(job.priority, 10);
+        assert_eq!(job.retry_limit, 5);
+    }
+    assert!(c.fetch_job("billing").await.unwrap().is_none());
+
+    assert!(c.unsubscribe(event, "emails").await.unwrap());
+    assert!(!c.unsubscribe(event, "emails").await.unwrap());
+    let ids = c.publish_data(event, json!({"user_id": 2})).await.unwrap();
+    assert_eq!(ids.len(), 1);
+    let job = c.fetch_job("analytics").await.unwrap().unwrap();
+    assert_eq!(job.data, json!({"user_id": 2}));
+    assert!(c.fetch_job("emails").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn subscribe_queue_does_not_exist() {
+    let c = prepare("subscribe_queue_does_not_exist", "jobtype").await;
+    let err = c
+        .subscribe("some_event", "does_not_exist")
+        .await
+        .unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "queue does not exist");
+    } else {
+        unreachable!()
+    }
+}
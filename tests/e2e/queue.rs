@@ -2,8 +2,10 @@ use std::time::Duration;
 
 use crate::utils::{self, POSRGRES_URL};
 use chrono::Utc;
-use pgboss::{Client, Queue, QueuePolicy};
+use pgboss::{Backoff, Client, Error, Job, JobState, Queue, QueuePolicy};
+use serde_json::json;
 use sqlx::postgres::PgPoolOptions;
+use sqlx::Connection;
 
 #[tokio::test]
 async fn simple_connect() {
@@ -97,6 +99,39 @@ async fn app_latest_version_already_exists() {
     let _c = Client::builder().schema(local).connect().await.unwrap();
 }
 
+#[tokio::test]
+async fn app_upgraded_while_in_use() {
+    let local = "app_upgraded_while_in_use";
+    utils::drop_schema(local).await.unwrap();
+    let c = Client::builder().schema(local).connect().await.unwrap();
+    c.create_standard_queue("jobtype").await.unwrap();
+
+    // as if installed by an earlier version of this crate
+    utils::ad_hoc_sql([format!("ALTER TABLE {local}.queue DROP COLUMN paused;")])
+        .await
+        .unwrap();
+    Client::builder().schema(local).connect().await.unwrap();
+    utils::ad_hoc_sql([format!("SELECT paused FROM {local}.queue;")])
+        .await
+        .unwrap();
+
+    // nothing to upgrade, so readers do not get in the way of (re)starting clients
+    let mut conn = sqlx::PgConnection::connect(&POSRGRES_URL).await.unwrap();
+    let mut tx = conn.begin().await.unwrap();
+    for table in ["queue", "job", "archive"] {
+        sqlx::query(&format!("SELECT * FROM {local}.{table};"))
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+    }
+    let connecting = Client::builder().schema(local).connect();
+    tokio::time::timeout(Duration::from_secs(10), connecting)
+        .await
+        .expect("not blocked by readers")
+        .unwrap();
+    tx.rollback().await.unwrap();
+}
+
 #[tokio::test]
 #[should_panic(expected = "Cannot migrate from the currently installed PgBoss application.")]
 async fn less_than_v21_app_already_exists() {
@@ -182,7 +217,7 @@ async fn create_non_standard_queue() {
         .retry_backoff(true)
         .expire_in(Duration::from_secs(60 * 60))
         .retain_for(Duration::from_secs(60 * 60 * 24))
-        .dead_letter(&dlq_opts.name)
+        .dead_letter(dlq_opts.name)
         .build();
 
     client.create_queue(&queue_opts).await.unwrap();
@@ -198,12 +233,60 @@ async fn create_non_standard_queue() {
     assert_eq!(q.policy, QueuePolicy::Singleton);
     assert_eq!(q.retry_limit.unwrap(), 3);
     assert_eq!(q.retry_delay.unwrap(), Duration::from_secs(10));
-    assert_eq!(q.retry_backoff.unwrap(), true);
+    assert!(q.retry_backoff.unwrap());
     assert_eq!(q.expire_in.unwrap(), Duration::from_secs(60 * 60));
     assert_eq!(q.retain_for.unwrap(), Duration::from_secs(60 * 60 * 24));
     assert_eq!(q.dead_letter.as_ref().unwrap(), dlq_opts.name);
 }
 
+#[tokio::test]
+async fn queue_backoff() {
+    let local = "queue_backoff";
+    utils::drop_schema(local).await.unwrap();
+
+    let client = Client::builder().schema(local).connect().await.unwrap();
+    let queue = Queue::builder()
+        .name("job_type")
+        .retry_backoff(true)
+        .backoff(Backoff::Linear)
+        .build();
+    client.create_queue(&queue).await.unwrap();
+    let q = client.get_queue("job_type").await.unwrap().unwrap();
+    assert_eq!(q.backoff, Some(Backoff::Linear));
+
+    // the strategy of the queue takes precedence over its `retry_backoff`...
+    let id = client.send_data("job_type", json!({})).await.unwrap();
+    let job = client.get_job("job_type", id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Linear);
+    // ... but not over the settings of the job
+    let job = Job::builder()
+        .queue_name("job_type")
+        .retry_backoff(false)
+        .build();
+    let id = client.send_job(job).await.unwrap();
+    let job = client.get_job("job_type", id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Fixed);
+    let max_delay = Duration::from_secs(60);
+    let job = Job::builder()
+        .queue_name("job_type")
+        .backoff(Backoff::DecorrelatedJitter { max_delay })
+        .build();
+    let id = client.send_job(job).await.unwrap();
+    let job = client.get_job("job_type", id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::DecorrelatedJitter { max_delay });
+
+    let queue = Queue::builder()
+        .name("job_type")
+        .retry_backoff(true)
+        .build();
+    let update = client.update_queue(&queue).await.unwrap();
+    assert_eq!(update.changes(), vec!["backoff"]);
+    assert_eq!(update.after.backoff, None);
+    let id = client.send_data("job_type", json!({})).await.unwrap();
+    let job = client.get_job("job_type", id).await.unwrap().unwrap();
+    assert_eq!(job.backoff, Backoff::Exponential);
+}
+
 #[tokio::test]
 async fn delete_queue() {
     let local = "delete_queue";
@@ -241,3 +324,183 @@ async fn delete_queue() {
         .expect("no error")
         .is_some());
 }
+
+#[tokio::test]
+async fn update_queue() {
+    let local = "update_queue";
+    utils::drop_schema(local).await.unwrap();
+
+    let client = Client::builder().schema(local).connect().await.unwrap();
+    client.create_standard_queue("job_type").await.unwrap();
+    client.create_standard_queue("job_type_dlq").await.unwrap();
+
+    let queue = Queue::builder()
+        .name("job_type")
+        .policy(QueuePolicy::Singleton)
+        .retry_limit(5)
+        .expire_in(Duration::from_secs(60))
+        .dead_letter("job_type_dlq")
+        .build();
+    let update = client.update_queue(&queue).await.unwrap();
+    assert_eq!(
+        update.changes(),
+        vec!["policy", "retry_limit", "expire_in", "dead_letter"]
+    );
+    assert_eq!(update.before.retry_limit, None);
+    assert!(update.after.updated_at > update.before.updated_at);
+
+    let q = client.get_queue("job_type").await.unwrap().unwrap();
+    assert_eq!(q.policy, QueuePolicy::Singleton);
+    assert_eq!(q.retry_limit, Some(5));
+    assert_eq!(q.expire_in, Some(Duration::from_secs(60)));
+    assert_eq!(q.dead_letter.as_deref(), Some("job_type_dlq"));
+    assert_eq!(q.created_at, update.before.created_at);
+
+    // jobs sent after the update are getting the new settings
+    let id = client
+        .send_data("job_type", serde_json::json!({}))
+        .await
+        .unwrap();
+    let job = client.get_job("job_type", id).await.unwrap().unwrap();
+    assert_eq!(job.retry_limit, 5);
+    assert_eq!(job.dead_letter.as_deref(), Some("job_type_dlq"));
+
+    // nothing to change
+    let update = client.update_queue(&queue).await.unwrap();
+    assert!(!update.is_changed());
+
+    // unset options are reset
+    let queue = Queue::builder().name("job_type").retry_limit(1).build();
+    let update = client.update_queue(&queue).await.unwrap();
+    assert_eq!(
+        update.changes(),
+        vec!["policy", "retry_limit", "expire_in", "dead_letter"]
+    );
+    assert_eq!(update.after.dead_letter, None);
+}
+
+#[tokio::test]
+async fn update_queue_does_not_exist() {
+    let local = "update_queue_does_not_exist";
+    utils::drop_schema(local).await.unwrap();
+
+    let client = Client::builder().schema(local).connect().await.unwrap();
+    let queue = Queue::builder().name("job_type").build();
+    let err = client.update_queue(&queue).await.unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "queue does not exist");
+    } else {
+        unreachable!()
+    }
+
+    client.create_standard_queue("job_type").await.unwrap();
+    let queue = Queue::builder()
+        .name("job_type")
+        .retry_limit(3)
+        .dead_letter("job_type_dlq")
+        .build();
+    let err = client.update_queue(&queue).await.unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "dead letter queue does not exist");
+    } else {
+        unreachable!()
+    }
+    // left intact
+    let q = client.get_queue("job_type").await.unwrap().unwrap();
+    assert_eq!(q.retry_limit, None);
+}
+
+#[tokio::test]
+async fn get_queue_size_and_stats() {
+    let local = "get_queue_size_and_stats";
+    let qname = "job_type";
+    let c = crate::utils::prepare(local, qname).await;
+    c.create_standard_queue("empty").await.unwrap();
+
+    assert_eq!(c.get_queue_size(qname, JobState::Active).await.unwrap(), 0);
+    let stats = c.get_queue_stats(qname).await.unwrap().unwrap();
+    assert_eq!(stats.queue_name, qname);
+    assert_eq!(stats.created + stats.deferred + stats.active, 0);
+
+    let job = Job::builder().queue_name(qname).retry_limit(1).build();
+    let no_retries = Job::builder().queue_name(qname).retry_limit(0).build();
+    c.send_job(&job).await.unwrap(); // will stay active
+    let retried = c.send_job(&job).await.unwrap();
+    let failed = c.send_job(&no_retries).await.unwrap();
+    let completed = c.send_job(&job).await.unwrap();
+    let cancelled = c.send_job(&job).await.unwrap();
+    assert_eq!(c.fetch_jobs(qname, 5).await.unwrap().len(), 5);
+    c.fail_job(qname, retried).await.unwrap();
+    c.fail_job(qname, failed).await.unwrap();
+    c.complete_job(qname, completed, json!({})).await.unwrap();
+    c.cancel_job(qname, cancelled).await.unwrap();
+    c.send_job(&job).await.unwrap();
+    c.send_job(&job).await.unwrap();
+    let deferred = Job::builder()
+        .queue_name(qname)
+        .delay_for(Duration::from_secs(60))
+        .build();
+    c.send_job(&deferred).await.unwrap();
+
+    let stats = c.get_queue_stats(qname).await.unwrap().unwrap();
+    assert_eq!(stats.created, 2);
+    assert_eq!(stats.deferred, 1);
+    assert_eq!(stats.retry, 1);
+    assert_eq!(stats.active, 1);
+    assert_eq!(stats.completed, 1);
+    assert_eq!(stats.cancelled, 1);
+    assert_eq!(stats.failed, 1);
+
+    // created (including deferred) and retry
+    assert_eq!(c.get_queue_size(qname, JobState::Active).await.unwrap(), 4);
+    // ... plus active
+    assert_eq!(
+        c.get_queue_size(qname, JobState::Completed).await.unwrap(),
+        5
+    );
+    assert_eq!(
+        c.get_queue_size("does_not_exist", JobState::Active)
+            .await
+            .unwrap(),
+        0
+    );
+
+    assert!(c.get_queue_stats("does_not_exist").await.unwrap().is_none());
+    let all = c.get_all_queue_stats().await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].queue_name, "empty");
+    assert_eq!(all[0].created, 0);
+    assert_eq!(all[1], stats);
+}
+
+#[tokio::test]
+async fn pause_and_resume_queue() {
+    let local = "pause_and_resume_queue";
+    let qname = "job_type";
+    let c = crate::utils::prepare(local, qname).await;
+    c.create_standard_queue("other").await.unwrap();
+    assert!(!c.get_queue(qname).await.unwrap().unwrap().paused);
+
+    let active = c.send_data(qname, json!({})).await.unwrap();
+    c.fetch_job(qname).await.unwrap().unwrap();
+
+    assert!(c.pause_queue(qname).await.unwrap());
+    assert!(c.get_queue(qname).await.unwrap().unwrap().paused);
+
+    // still accepting jobs, but not handing them out
+    let id = c.send_data(qname, json!({})).await.unwrap();
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+    assert_eq!(c.get_queue_size(qname, JobState::Active).await.unwrap(), 1);
+
+    // active jobs can still be completed and other queues are not affected
+    assert!(c.complete_job(qname, active, json!({})).await.unwrap());
+    c.send_data("other", json!({})).await.unwrap();
+    assert!(c.fetch_job("other").await.unwrap().is_some());
+
+    assert!(c.resume_queue(qname).await.unwrap());
+    assert!(!c.get_queue(qname).await.unwrap().unwrap().paused);
+    assert_eq!(c.fetch_job(qname).await.unwrap().unwrap().id, id);
+
+    assert!(!c.pause_queue("does_not_exist").await.unwrap());
+    assert!(!c.resume_queue("does_not_exist").await.unwrap());
+}
@@ -0,0 +1,167 @@
+use crate::utils::{self, prepare};
+use pgboss::{Error, Job, Schedule};
+use serde_json::json;
+use sqlx::Connection;
+use std::time::Duration;
+
+#[tokio::test]
+async fn schedule_and_unschedule() {
+    let qname = "jobtype";
+    let c = prepare("schedule_and_unschedule", qname).await;
+    assert!(c.get_schedules().await.unwrap().is_empty());
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .data(json!({"report": "daily"}))
+        .priority(10)
+        .build();
+    let schedule = Schedule::builder()
+        .cron("0 8 * * *")
+        .timezone("Europe/Berlin")
+        .job(job.clone())
+        .build();
+    c.schedule(&schedule).await.unwrap();
+
+    let schedules = c.get_schedules().await.unwrap();
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules[0].queue_name, qname);
+    assert_eq!(schedules[0].cron, "0 8 * * *");
+    assert_eq!(schedules[0].timezone.as_deref(), Some("Europe/Berlin"));
+    assert_eq!(schedules[0].data, Some(json!({"report": "daily"})));
+    assert_eq!(schedules[0].options.as_ref().unwrap()["priority"], 10);
+    let created_at = schedules[0].created_at;
+
+    // one schedule per queue, so this is an update
+    let schedule = Schedule::builder().cron("*/5 * * * *").job(job).build();
+    c.schedule(&schedule).await.unwrap();
+    let schedules = c.get_schedules().await.unwrap();
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules[0].cron, "*/5 * * * *");
+    assert_eq!(schedules[0].timezone, None);
+    assert_eq!(schedules[0].created_at, created_at);
+    assert!(schedules[0].updated_at > created_at);
+
+    assert!(c.unschedule(qname).await.unwrap());
+    assert!(!c.unschedule(qname).await.unwrap());
+    assert!(c.get_schedules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn schedule_invalid() {
+    let qname = "jobtype";
+    let c = prepare("schedule_invalid", qname).await;
+
+    let job = Job::builder().queue_name(qname).build();
+    let schedule = Schedule::builder()
+        .cron("every day")
+        .job(job.clone())
+        .build();
+    let err = c.schedule(&schedule).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidSchedule { .. }));
+
+    let schedule = Schedule::builder()
+        .cron("0 8 * * *")
+        .timezone("Mars/Olympus_Mons")
+        .job(job)
+        .build();
+    let err = c.schedule(&schedule).await.unwrap_err();
+    if let Error::InvalidSchedule { msg } = err {
+        assert_eq!(msg, "unknown time zone: Mars/Olympus_Mons");
+    } else {
+        unreachable!()
+    }
+
+    let job = Job::builder().queue_name("does_not_exist").build();
+    let schedule = Schedule::builder().cron("0 8 * * *").job(job).build();
+    let err = c.schedule(&schedule).await.unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "queue does not exist");
+    } else {
+        unreachable!()
+    }
+    assert!(c.get_schedules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn schedulers_send_one_job_per_tick() {
+    let local = "schedulers_send_one_job_per_tick";
+    let qname = "jobtype";
+    let c = prepare(local, qname).await;
+
+    let job = Job::builder()
+        .queue_name(qname)
+        .data(json!({"scheduled": true}))
+        .priority(5)
+        .build();
+    let schedule = Schedule::builder().cron("* * * * *").job(job).build();
+    c.schedule(&schedule).await.unwrap();
+
+    let interval = Duration::from_millis(100);
+    let scheduler1 = c.scheduler(interval);
+    let _scheduler2 = c.scheduler(interval);
+    assert_eq!(scheduler1.interval(), interval);
+
+    // pretend the schedules were last checked over a minute ago, so that
+    // the cron expression has fired since then, until one of the schedulers
+    // picks this up (it could have overwritten our update with its own check)
+    let mut jobs = Vec::new();
+    for _ in 0..10 {
+        utils::ad_hoc_sql([format!(
+            "UPDATE {local}.version SET cron_on = now() - interval '61 seconds';"
+        )])
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        jobs = c.fetch_jobs(qname, 10).await.unwrap();
+        if !jobs.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].data, json!({"scheduled": true}));
+    assert_eq!(jobs[0].priority, 5);
+
+    // let the schedulers check a few more times
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn scheduler_does_not_move_cron_on_backwards() {
+    let local = "scheduler_does_not_move_cron_on_backwards";
+    let qname = "jobtype";
+    let c = prepare(local, qname).await;
+
+    let job = Job::builder().queue_name(qname).build();
+    let schedule = Schedule::builder().cron("* * * * *").job(job).build();
+    c.schedule(&schedule).await.unwrap();
+
+    // as if another scheduler, whose transaction started later, has already checked the schedules
+    utils::ad_hoc_sql([format!(
+        "UPDATE {local}.version SET cron_on = now() + interval '1 hour';"
+    )])
+    .await
+    .unwrap();
+    let _scheduler = c.scheduler(Duration::from_millis(100));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let mut conn = sqlx::PgConnection::connect(&utils::POSRGRES_URL)
+        .await
+        .unwrap();
+    let in_future: bool = sqlx::query_scalar(&format!(
+        "SELECT cron_on > now() + interval '59 minutes' FROM {local}.version;"
+    ))
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+    assert!(in_future);
+    assert!(c.fetch_job(qname).await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[should_panic(expected = "interval should be greater than zero")]
+async fn scheduler_zero_interval() {
+    let qname = "jobtype";
+    let c = prepare("scheduler_zero_interval", qname).await;
+    c.scheduler(Duration::ZERO);
+}
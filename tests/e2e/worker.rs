@@ -0,0 +1,224 @@
+use std::sync::{atomic, Arc};
+use std::time::Duration;
+
+use crate::utils::{self, prepare};
+use pgboss::{Client, Error, Job, JobDetails, JobState};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+async fn wait_for_state(c: &Client, qname: &str, id: Uuid, state: JobState) -> JobDetails {
+    for _ in 0..50 {
+        let job = c.get_job(qname, id).await.unwrap().unwrap();
+        if job.state == state {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("job {} did not transition to '{}' in time", id, state);
+}
+
+#[tokio::test]
+async fn worker_completes_jobs() {
+    let qname = "jobtype";
+    let c = prepare("worker_completes_jobs", qname).await;
+
+    let id1 = c.send_data(qname, json!({"n": 1})).await.unwrap();
+    let id2 = c.send_data(qname, json!({"n": 2})).await.unwrap();
+    let id3 = c.send_data(qname, json!({"n": 3})).await.unwrap();
+
+    let processed = Arc::new(atomic::AtomicUsize::new(0));
+    let counter = processed.clone();
+    let worker = c
+        .worker()
+        .register(qname, move |job: JobDetails| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, atomic::Ordering::SeqCst);
+                Ok::<_, Error>(json!({"doubled": job.data["n"].as_u64().unwrap() * 2}))
+            }
+        })
+        .concurrency(2)
+        .poll_interval(Duration::from_millis(100))
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    let job = wait_for_state(&c, qname, id1, JobState::Completed).await;
+    assert_eq!(job.output.unwrap(), json!({"doubled": 2}));
+    let job = wait_for_state(&c, qname, id2, JobState::Completed).await;
+    assert_eq!(job.output.unwrap(), json!({"doubled": 4}));
+    let job = wait_for_state(&c, qname, id3, JobState::Completed).await;
+    assert_eq!(job.output.unwrap(), json!({"doubled": 6}));
+    assert_eq!(processed.load(atomic::Ordering::SeqCst), 3);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn worker_fails_jobs() {
+    let qname = "jobtype";
+    let c = prepare("worker_fails_jobs", qname).await;
+
+    let job = Job::builder().queue_name(qname).retry_limit(0).build();
+    let id1 = c.send_job(&job).await.unwrap();
+    let id2 = c.send_job(&job).await.unwrap();
+
+    let worker = c
+        .worker()
+        .register(qname, move |job: JobDetails| async move {
+            if job.id == id1 {
+                return Err("cannot process this one");
+            }
+            panic!("unexpected job");
+            #[allow(unreachable_code)]
+            Ok(())
+        })
+        .poll_interval(Duration::from_millis(100))
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    let job = wait_for_state(&c, qname, id1, JobState::Failed).await;
+    assert_eq!(
+        job.output.unwrap(),
+        json!({"message": "cannot process this one"})
+    );
+
+    // panics are caught and the job is failed
+    let job = wait_for_state(&c, qname, id2, JobState::Failed).await;
+    assert!(job.output.unwrap()["message"]
+        .as_str()
+        .unwrap()
+        .contains("panicked"));
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn worker_queue_does_not_exist() {
+    let local = "worker_queue_does_not_exist";
+    utils::drop_schema(local).await.unwrap();
+
+    let c = Client::builder().schema(local).connect().await.unwrap();
+    let err = c
+        .worker()
+        .register("jobtype", |_job: JobDetails| async { Ok::<_, Error>(()) })
+        .build()
+        .run()
+        .await
+        .unwrap_err();
+    if let Error::DoesNotExist { msg } = err {
+        assert_eq!(msg, "queue does not exist");
+    } else {
+        unreachable!()
+    }
+}
+
+#[tokio::test]
+async fn worker_graceful_shutdown() {
+    let qname = "jobtype";
+    let c = prepare("worker_graceful_shutdown", qname).await;
+
+    let fast = c.send_data(qname, json!({"sleep_ms": 10})).await.unwrap();
+    let slow = c
+        .send_data(qname, json!({"sleep_ms": 60_000}))
+        .await
+        .unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let worker = c
+        .worker()
+        .register(qname, |job: JobDetails| async move {
+            let ms = job.data["sleep_ms"].as_u64().unwrap();
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            Ok::<_, Error>(())
+        })
+        .concurrency(2)
+        .poll_interval(Duration::from_millis(100))
+        .with_graceful_shutdown(async {
+            let _ = rx.await;
+        })
+        .shutdown_timeout(Duration::from_millis(500))
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    wait_for_state(&c, qname, fast, JobState::Completed).await;
+    wait_for_state(&c, qname, slow, JobState::Active).await;
+
+    tx.send(()).unwrap();
+    let details = handle.await.unwrap().unwrap();
+    assert_eq!(details.released_jobs, vec![slow]);
+
+    // the slow job is back in the queue and can be consumed right away
+    let job = c.fetch_job(qname).await.unwrap().unwrap();
+    assert_eq!(job.id, slow);
+}
+
+#[tokio::test]
+async fn worker_graceful_shutdown_all_jobs_finished() {
+    let qname = "jobtype";
+    let c = prepare("worker_graceful_shutdown_all_jobs_finished", qname).await;
+
+    let id = c.send_data(qname, json!({})).await.unwrap();
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let worker = c
+        .worker()
+        .register(qname, |_job: JobDetails| async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok::<_, Error>(())
+        })
+        .poll_interval(Duration::from_millis(100))
+        .with_graceful_shutdown(async {
+            let _ = rx.await;
+        })
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    wait_for_state(&c, qname, id, JobState::Active).await;
+    tx.send(()).unwrap();
+    let details = handle.await.unwrap().unwrap();
+    assert!(details.released_jobs.is_empty());
+
+    let job = c.get_job(qname, id).await.unwrap().unwrap();
+    assert_eq!(job.state, JobState::Completed);
+}
+
+#[derive(Debug, Deserialize)]
+struct Sum {
+    a: u64,
+    b: u64,
+}
+
+#[tokio::test]
+async fn worker_typed_handler() {
+    let qname = "jobtype";
+    let c = prepare("worker_typed_handler", qname).await;
+
+    let good = c.send_data(qname, json!({"a": 1, "b": 2})).await.unwrap();
+    let job = Job::builder()
+        .queue_name(qname)
+        .data(json!({"a": "one"}))
+        .retry_limit(0)
+        .build();
+    let bad = c.send_job(&job).await.unwrap();
+
+    let worker = c
+        .worker()
+        .register_typed(qname, |job: JobDetails<Sum>| async move {
+            Ok::<_, Error>(json!({"sum": job.data.a + job.data.b}))
+        })
+        .poll_interval(Duration::from_millis(100))
+        .build();
+    let handle = tokio::spawn(worker.run());
+
+    let job = wait_for_state(&c, qname, good, JobState::Completed).await;
+    assert_eq!(job.output.unwrap(), json!({"sum": 3}));
+
+    let job = wait_for_state(&c, qname, bad, JobState::Failed).await;
+    assert!(job.output.unwrap()["message"]
+        .as_str()
+        .unwrap()
+        .contains("failed to decode payload"));
+
+    handle.abort();
+}